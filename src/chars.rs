@@ -0,0 +1,163 @@
+//! Lazy transliteration of a `&str` or of an iterator of characters one character at a time
+
+use crate::{Options, Scheme, scheme::convert, utils::Context};
+use alloc::string::String;
+use core::{iter::Peekable, str::Chars};
+
+/// Extension trait to transliterate a `&str` lazily.
+pub trait Transliterate {
+    /// This function returns an iterator over the characters of `self` converted from `from` to
+    /// `to`.
+    ///
+    /// Nothing is converted until the iterator is advanced, and then only one word at a time, so
+    /// taking the first few characters of a huge text does not convert all of it.
+    ///
+    /// ```
+    /// use uast::{Scheme, Transliterate};
+    ///
+    /// let s = "bhargo devasya dhīmahi";
+    /// assert_eq!(
+    ///     "भर्गो",
+    ///     s.transliterate_chars(Scheme::Iast, Scheme::Devanāgarī)
+    ///         .take_while(|c| !c.is_whitespace())
+    ///         .collect::<String>()
+    /// );
+    /// ```
+    fn transliterate_chars(&self, from: Scheme, to: Scheme) -> TransliteratedChars<Chars<'_>> {
+        self.transliterate_chars_with(from, to, &Options::default())
+    }
+
+    /// This function is [`transliterate_chars`](Self::transliterate_chars) with [`Options`].
+    ///
+    /// ```
+    /// use uast::{Options, Scheme, Transliterate, Unknown};
+    ///
+    /// let opts = Options {
+    ///     unknown: Unknown::PassThrough,
+    ///     ..Options::default()
+    /// };
+    /// assert_eq!(
+    ///     "देवx",
+    ///     "devax"
+    ///         .transliterate_chars_with(Scheme::Iast, Scheme::Devanāgarī, &opts)
+    ///         .collect::<String>()
+    /// );
+    /// ```
+    fn transliterate_chars_with(
+        &self,
+        from: Scheme,
+        to: Scheme,
+        opts: &Options,
+    ) -> TransliteratedChars<Chars<'_>>;
+}
+
+impl Transliterate for str {
+    fn transliterate_chars_with(
+        &self,
+        from: Scheme,
+        to: Scheme,
+        opts: &Options,
+    ) -> TransliteratedChars<Chars<'_>> {
+        TransliteratedChars::new(self.chars(), from, to, opts)
+    }
+}
+
+/// Extension trait to transliterate an iterator of characters lazily, which it takes by value.
+pub trait TransliterateIter: Iterator<Item = char> + Sized {
+    /// This function returns an iterator over the characters of `self` converted from `from` to
+    /// `to`, which reads them one word at a time as they are needed.
+    ///
+    /// ```
+    /// use uast::{Scheme, TransliterateIter};
+    ///
+    /// let s = "Bargo devasya";
+    /// assert_eq!(
+    ///     "भर्गो देवस्य",
+    ///     s.chars()
+    ///         .transliterate_chars(Scheme::Slp1, Scheme::Devanāgarī)
+    ///         .collect::<String>()
+    /// );
+    /// ```
+    fn transliterate_chars(self, from: Scheme, to: Scheme) -> TransliteratedChars<Self> {
+        self.transliterate_chars_with(from, to, &Options::default())
+    }
+
+    /// This function is [`transliterate_chars`](Self::transliterate_chars) with [`Options`].
+    ///
+    /// ```
+    /// use uast::{Options, Scheme, TransliterateIter, Unknown};
+    ///
+    /// let opts = Options {
+    ///     unknown: Unknown::PassThrough,
+    ///     ..Options::default()
+    /// };
+    /// assert_eq!(
+    ///     "भर्गो देवस्य!",
+    ///     "Bargo devasya!"
+    ///         .chars()
+    ///         .transliterate_chars_with(Scheme::Slp1, Scheme::Devanāgarī, &opts)
+    ///         .collect::<String>()
+    /// );
+    /// ```
+    fn transliterate_chars_with(
+        self,
+        from: Scheme,
+        to: Scheme,
+        opts: &Options,
+    ) -> TransliteratedChars<Self> {
+        TransliteratedChars::new(self, from, to, opts)
+    }
+}
+
+impl<I: Iterator<Item = char>> TransliterateIter for I {}
+
+/// Iterator returned by [`Transliterate::transliterate_chars`] and
+/// [`TransliterateIter::transliterate_chars`].
+pub struct TransliteratedChars<I: Iterator<Item = char>> {
+    rest: Peekable<I>,
+    ctx: Context,
+    word: String,
+    buf: String,
+    pos: usize,
+}
+
+impl<I: Iterator<Item = char>> TransliteratedChars<I> {
+    fn new(chars: I, from: Scheme, to: Scheme, opts: &Options) -> Self {
+        Self {
+            rest: chars.peekable(),
+            ctx: Context::new(*opts, from, to),
+            word: String::new(),
+            buf: String::new(),
+            pos: 0,
+        }
+    }
+}
+
+impl<I: Iterator<Item = char>> Iterator for TransliteratedChars<I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        loop {
+            if let Some(c) = self.buf[self.pos..].chars().next() {
+                self.pos += c.len_utf8();
+                return Some(c);
+            }
+
+            self.rest.peek()?;
+
+            // the leading whitespace and the word after it
+            self.word.clear();
+            while let Some(c) = self.rest.next_if(|c| c.is_whitespace()) {
+                self.word.push(c);
+            }
+            while let Some(c) = self.rest.next_if(|c| !c.is_whitespace()) {
+                self.word.push(c);
+            }
+
+            self.buf.clear();
+            self.pos = 0;
+            convert(&self.word, &mut self.ctx, &mut self.buf)
+                .expect("writing to a String does not fail");
+        }
+    }
+}
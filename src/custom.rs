@@ -0,0 +1,310 @@
+//! Schemes defined at runtime, for example in a TOML or JSON file, or by other crates
+
+use crate::{
+    DefinitionError, SchemeIndex,
+    tables::{Category, Entry},
+};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::fmt;
+
+/// Whether a scheme is an abugida like देवनागरी or an alphabet like IAST.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum ScriptKind {
+    /// Every देवनागरी character has a character of its own, so text is converted character by
+    /// character.
+    Brahmic,
+    /// Consonants carry no inherent vowel. It has to be written out, and a consonant without a
+    /// vowel gets a halanta in देवनागरी.
+    #[default]
+    Roman,
+}
+
+/// How a देवनागरी character is spelt in a [`Definition`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum Spelling {
+    /// The only spelling.
+    One(String),
+    /// Every accepted spelling. The first one is written, all of them are read.
+    Alternatives(Vec<String>),
+}
+
+impl Spelling {
+    fn iter(&self) -> impl Iterator<Item = &String> {
+        match self {
+            Spelling::One(v) => core::slice::from_ref(v).iter(),
+            Spelling::Alternatives(v) => v.iter(),
+        }
+    }
+}
+
+/// The letters of a scheme, keyed by the देवनागरी character they stand for.
+///
+/// The categories are those of [`tables`](crate::tables). `escapes` maps a sequence of the input
+/// to the letters it is read as, like `/a/` to `ā` in UAST-IO.
+///
+/// In TOML:
+///
+/// ```toml
+/// name = "Simple"
+/// kind = "roman"
+///
+/// [vowels]
+/// "अ" = "a"
+/// "आ" = ["aa", "A"]
+///
+/// [vowel_signs]
+/// "ा" = ["aa", "A"]
+///
+/// [consonants]
+/// "क" = "k"
+///
+/// [escapes]
+/// "{A}" = "aa"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct Definition {
+    /// Name of the scheme, used in errors and diagnostics.
+    pub name: String,
+    /// Whether the scheme is Brahmic or Roman.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub kind: ScriptKind,
+    /// Independent vowels.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub vowels: BTreeMap<String, Spelling>,
+    /// Dependent vowels.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub vowel_signs: BTreeMap<String, Spelling>,
+    /// Consonants.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub consonants: BTreeMap<String, Spelling>,
+    /// Avagraha, daṇḍas, digits and the like.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub misc: BTreeMap<String, Spelling>,
+    /// Anusvāra, visarga, candrabindu, halanta, ॐ and the like.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub specials: BTreeMap<String, Spelling>,
+    /// Sequences of the input that are read as other letters.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub escapes: BTreeMap<String, String>,
+}
+
+/// The letters of a scheme, to be used with [`Scheme::Custom`](crate::Scheme::Custom).
+///
+/// A scheme only lists its letters. They are read and written by the same engine that reads
+/// UAST-IO and writes IAST: a Roman scheme writes the inherent vowel after a consonant that has no
+/// vowel sign, and reads a consonant without a vowel as one with a halanta.
+///
+/// ```
+/// use std::sync::Arc;
+/// use uast::{
+///     Scheme, SchemeDefinition, ScriptKind,
+///     tables::{Category, Entry},
+///     transliterate,
+/// };
+///
+/// #[derive(Debug)]
+/// struct Tiny(Vec<Entry>);
+///
+/// impl SchemeDefinition for Tiny {
+///     fn name(&self) -> &str {
+///         "Tiny"
+///     }
+///
+///     fn kind(&self) -> ScriptKind {
+///         ScriptKind::Roman
+///     }
+///
+///     fn entries(&self) -> &[Entry] {
+///         &self.0
+///     }
+/// }
+///
+/// let entry = |category, text: &str, devanāgarī| Entry {
+///     category,
+///     text: text.to_string(),
+///     devanāgarī,
+/// };
+/// let tiny = Tiny(vec![
+///     entry(Category::Vowel, "a", 'अ'),
+///     entry(Category::VowelSign, "o", 'ो'),
+///     entry(Category::Consonant, "n", 'न'),
+///     entry(Category::Consonant, "m", 'म'),
+///     entry(Category::Misc, "|", '।'),
+/// ]);
+/// let tiny = Scheme::Custom(Arc::new(tiny));
+///
+/// assert_eq!("नमो।", transliterate("namo|", tiny.clone(), Scheme::Devanāgarī));
+/// assert_eq!("namo|", transliterate("नमो।", Scheme::Devanāgarī, tiny));
+/// ```
+pub trait SchemeDefinition: fmt::Debug + Send + Sync {
+    /// Name of the scheme, used in errors and diagnostics.
+    fn name(&self) -> &str;
+
+    /// Whether the scheme is Brahmic or Roman.
+    fn kind(&self) -> ScriptKind;
+
+    /// Every spelling of every letter. Of the spellings of a देवनागरी character, the first one is
+    /// written and all of them are read.
+    fn entries(&self) -> &[Entry];
+
+    /// Every escape along with the letters it is read as.
+    fn escapes(&self) -> &[(String, String)] {
+        &[]
+    }
+
+    /// The entries and escapes arranged for lookups. A scheme that does not keep a
+    /// [`SchemeIndex`] has one built every time it is converted from or to.
+    fn index(&self) -> Option<&SchemeIndex> {
+        None
+    }
+}
+
+/// A scheme loaded from a [`Definition`], to be used with [`Scheme::Custom`](crate::Scheme::Custom).
+///
+/// Its letters are arranged for lookups once, when it is loaded, and a [`Scheme`](crate::Scheme)
+/// shares it through an [`Arc`](alloc::sync::Arc).
+///
+/// ```
+/// use std::{collections::BTreeMap, sync::Arc};
+/// use uast::{CustomScheme, Definition, Scheme, Spelling, transliterate};
+///
+/// let spell = |v: &[(&str, &str)]| {
+///     v.iter()
+///         .map(|(k, v)| (k.to_string(), Spelling::One(v.to_string())))
+///         .collect::<BTreeMap<_, _>>()
+/// };
+/// let def = Definition {
+///     name: "Simple".to_string(),
+///     vowels: spell(&[("अ", "a"), ("इ", "i")]),
+///     vowel_signs: spell(&[("ि", "i")]),
+///     consonants: spell(&[("क", "k"), ("व", "v")]),
+///     ..Default::default()
+/// };
+/// let simple = Scheme::Custom(Arc::new(CustomScheme::new(def).unwrap()));
+///
+/// assert_eq!("कवि", transliterate("kavi", simple.clone(), Scheme::Devanāgarī));
+/// assert_eq!("kavi", transliterate("kavi", Scheme::Iast, simple));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CustomScheme {
+    name: String,
+    kind: ScriptKind,
+    entries: Vec<Entry>,
+    escapes: Vec<(String, String)>,
+    index: SchemeIndex,
+}
+
+impl CustomScheme {
+    /// This function checks `def` and loads it.
+    pub fn new(def: Definition) -> Result<Self, DefinitionError> {
+        let mut entries = Vec::new();
+
+        for (category, map) in [
+            (Category::Vowel, def.vowels),
+            (Category::VowelSign, def.vowel_signs),
+            (Category::Consonant, def.consonants),
+            (Category::Misc, def.misc),
+            (Category::Special, def.specials),
+        ] {
+            for (k, v) in map {
+                let mut chars = k.chars();
+                let devanāgarī = match (chars.next(), chars.next()) {
+                    (Some(c), None) => c,
+                    _ => return Err(DefinitionError::NotOneCharacter(k)),
+                };
+
+                for text in v.iter() {
+                    if text.is_empty() || text.contains(char::is_whitespace) {
+                        return Err(DefinitionError::InvalidSpelling(text.clone()));
+                    }
+
+                    entries.push(Entry {
+                        category,
+                        text: text.clone(),
+                        devanāgarī,
+                    });
+                }
+            }
+        }
+
+        for (k, v) in &def.escapes {
+            if k.is_empty() || k.contains(char::is_whitespace) {
+                return Err(DefinitionError::InvalidSpelling(k.clone()));
+            }
+            if v.contains(char::is_whitespace) {
+                return Err(DefinitionError::InvalidSpelling(v.clone()));
+            }
+        }
+
+        // consonants are written with the inherent vowel of the scheme
+        if def.kind == ScriptKind::Roman
+            && !entries
+                .iter()
+                .any(|v| v.category == Category::Vowel && v.devanāgarī == 'अ')
+        {
+            return Err(DefinitionError::MissingInherentVowel);
+        }
+
+        let escapes = def.escapes.into_iter().collect::<Vec<_>>();
+        Ok(Self {
+            name: def.name,
+            kind: def.kind,
+            index: SchemeIndex::new(def.kind, &entries, &escapes),
+            entries,
+            escapes,
+        })
+    }
+
+    /// This function loads a [`Definition`] written in TOML.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(s: &str) -> Result<Self, DefinitionError> {
+        toml::from_str(s)
+            .map_err(|e| DefinitionError::Syntax(e.to_string()))
+            .and_then(Self::new)
+    }
+
+    /// This function loads a [`Definition`] written in JSON.
+    #[cfg(feature = "json")]
+    pub fn from_json(s: &str) -> Result<Self, DefinitionError> {
+        serde_json::from_str(s)
+            .map_err(|e| DefinitionError::Syntax(e.to_string()))
+            .and_then(Self::new)
+    }
+}
+
+impl SchemeDefinition for CustomScheme {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> ScriptKind {
+        self.kind
+    }
+
+    // in the order of the `Definition`
+    fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    fn escapes(&self) -> &[(String, String)] {
+        &self.escapes
+    }
+
+    fn index(&self) -> Option<&SchemeIndex> {
+        Some(&self.index)
+    }
+}
+
+impl TryFrom<Definition> for CustomScheme {
+    type Error = DefinitionError;
+
+    fn try_from(def: Definition) -> Result<Self, Self::Error> {
+        Self::new(def)
+    }
+}
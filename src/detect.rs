@@ -0,0 +1,294 @@
+//! Guessing the scheme of a text from the letters it uses
+
+#[cfg(feature = "iast")]
+use crate::tables;
+#[cfg(feature = "uast")]
+use crate::uast;
+use crate::{
+    Scheme, normalize,
+    utils::{is_devanāgarī, is_gujarātī, lowercase},
+};
+use alloc::{string::String, vec, vec::Vec};
+
+/// How likely a text is to be written in a [`Scheme`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Detection {
+    /// The scheme.
+    pub scheme: Scheme,
+    /// Between 0 and 1. The confidences of all the detections of a text add up to 1.
+    pub confidence: f32,
+}
+
+// every character the spellings of `scheme` are made of, sorted
+#[cfg(feature = "iast")]
+fn inventory(scheme: Scheme) -> Vec<char> {
+    let mut res = tables::entries(scheme)
+        .iter()
+        .flat_map(|v| v.text.chars())
+        .collect::<Vec<_>>();
+    res.sort_unstable();
+    res.dedup();
+
+    res
+}
+
+// evidence for each of the Roman schemes, and the characters each of them cannot read. the
+// evidence for the schemes that are not compiled in is counted but never read.
+#[derive(Default)]
+#[cfg_attr(
+    not(all(feature = "gu", feature = "uast", feature = "slp")),
+    allow(dead_code)
+)]
+struct Counts {
+    devanāgarī: usize,
+    gujarātī: usize,
+    roman: usize,
+    iast: usize,
+    slp: usize,
+    digraphs: usize,
+    escapes: usize,
+    unknown_iast: usize,
+    unknown_uast: usize,
+    unknown_slp: usize,
+    unknown_lookalikes: Vec<usize>,
+}
+
+// the characters each Roman scheme reads. the schemes that write with capitals much like SLP1
+// are weighed by the evidence for SLP1 when they read the text as well, along with whether they
+// spell aspirates and diphthongs with two letters like IAST does.
+#[derive(Default)]
+struct Inventories {
+    iast: Vec<char>,
+    uast: Vec<char>,
+    slp: Vec<char>,
+    lookalikes: Vec<(Scheme, Vec<char>, bool)>,
+}
+
+impl Counts {
+    fn roman(&mut self, word: &str, inventories: &Inventories) {
+        let Inventories {
+            iast,
+            uast,
+            slp,
+            lookalikes,
+        } = inventories;
+        let chars = word.chars().collect::<Vec<_>>();
+
+        for (i, &c) in chars.iter().enumerate() {
+            // digits and punctuation are shared by all of them
+            if !c.is_alphabetic() && !matches!(c, '/' | '~') {
+                continue;
+            }
+            self.roman += 1;
+
+            let l = lowercase(c);
+            let in_iast = iast.binary_search(&l).is_ok();
+            let in_slp = slp.binary_search(&c).is_ok();
+            self.unknown_iast += usize::from(!in_iast);
+            self.unknown_uast += usize::from(uast.binary_search(&l).is_err());
+            self.unknown_slp += usize::from(!in_slp);
+            for (n, (_, v, _)) in self.unknown_lookalikes.iter_mut().zip(lookalikes) {
+                *n += usize::from(v.binary_search(&c).is_err());
+            }
+
+            // IAST writes with diacritics what SLP1 writes with capitals and the letters IAST
+            // does not use. capitals at the start of a word may just be capitalisation.
+            if in_iast && !l.is_ascii() {
+                self.iast += 3;
+            }
+            if in_slp && !in_iast {
+                self.slp += 3;
+            }
+            if c.is_ascii_uppercase() {
+                self.slp += if i == 0 { 1 } else { 2 };
+            }
+
+            // aspirates and diphthongs are two letters in IAST but one in SLP1
+            let next = chars.get(i + 1).copied().map(lowercase);
+            match (l, next) {
+                ('b' | 'c' | 'd' | 'g' | 'j' | 'k' | 'p' | 't' | 'ḍ' | 'ṭ', Some('h'))
+                | ('a', Some('i' | 'u')) => self.digraphs += 1,
+                _ => (),
+            }
+        }
+
+        // a `/…/` escape is only ever UAST-IO
+        #[cfg(feature = "uast")]
+        {
+            let parts = word.split('/').collect::<Vec<_>>();
+            self.escapes += parts
+                .iter()
+                .skip(1)
+                .step_by(2)
+                .take((parts.len() - 1) / 2)
+                .filter(|k| uast::is_escape(k))
+                .count();
+        }
+    }
+}
+
+// the evidence for a scheme, less the more characters it cannot read
+#[cfg(feature = "iast")]
+fn score(evidence: f32, unknown: usize) -> f32 {
+    let n = 1.0 + unknown as f32;
+    evidence / (n * n)
+}
+
+/// This function guesses the [`Scheme`] of `text` from the letters it uses. It returns every
+/// scheme the text could be in, most likely first. Text without any letters gives nothing.
+///
+/// देवनागरी and ગુજરાતી are told apart by their Unicode blocks. Roman text is weighed by the
+/// letters only one of IAST, UAST-IO and SLP1 has: diacritics and `/…/` escapes, or the capitals
+/// and the letters like `f`, `q`, `w` and `x` of SLP1. Without any of those, aspirates and
+/// diphthongs like `bh` and `ai`, which are two letters in IAST but one in SLP1, tell them apart.
+/// Harvard-Kyoto, ITRANS and WX use capitals much like SLP1, so when one of them reads the text
+/// with as few unknown characters as SLP1 does, it is at least as likely as SLP1. Harvard-Kyoto
+/// and ITRANS spell aspirates and diphthongs with two letters like IAST, which makes them likelier
+/// than SLP1 for text with capitals that has them.
+/// ISO 15919, which reads much like IAST, Velthuis and custom schemes are never detected.
+///
+/// ```
+/// use uast::{Scheme, detect_scheme};
+///
+/// assert_eq!(Scheme::Devanāgarī, detect_scheme("भर्गो देवस्य")[0].scheme);
+/// assert_eq!(Scheme::Iast, detect_scheme("bhargo devasya dhīmahi")[0].scheme);
+/// assert_eq!(Scheme::Iast, detect_scheme("bhargo devasya dhimahi")[0].scheme);
+/// assert_eq!(Scheme::Slp1, detect_scheme("Bargo devasya DImahi")[0].scheme);
+/// assert_eq!(Scheme::UastIo, detect_scheme("/om/ bh/u/rbhuvah")[0].scheme);
+/// assert!(detect_scheme("1, 2, 3...").is_empty());
+/// ```
+pub fn detect_scheme(text: &str) -> Vec<Detection> {
+    // a scheme that is not compiled in reads nothing
+    #[cfg_attr(not(feature = "iast"), allow(unused_mut))]
+    let mut inventories = Inventories::default();
+    #[cfg(feature = "iast")]
+    {
+        inventories.iast = inventory(Scheme::Iast);
+    }
+    #[cfg(feature = "uast")]
+    {
+        inventories.uast = inventory(Scheme::UastIo);
+    }
+    #[cfg(feature = "slp")]
+    {
+        inventories.slp = inventory(Scheme::Slp1);
+        inventories.lookalikes = [
+            #[cfg(feature = "hk")]
+            (Scheme::HarvardKyoto, true),
+            #[cfg(feature = "itrans")]
+            (Scheme::Itrans, true),
+            #[cfg(feature = "wx")]
+            (Scheme::Wx, false),
+        ]
+        .into_iter()
+        .map(|(v, digraphs): (Scheme, bool)| (v.clone(), inventory(v), digraphs))
+        .collect();
+    }
+
+    let mut counts = Counts {
+        unknown_lookalikes: vec![0; inventories.lookalikes.len()],
+        ..Default::default()
+    };
+    let (mut buf, mut segments) = (String::new(), Vec::new());
+
+    for word in text.split_whitespace() {
+        // decomposed diacritics count as the letters they make up
+        let word = if normalize::nfc(word, &mut buf, &mut segments) {
+            buf.as_str()
+        } else {
+            word
+        };
+
+        let mut rest = word;
+        while let Some(i) = rest.find(|c| is_devanāgarī(c) || is_gujarātī(c)) {
+            if i > 0 {
+                counts.roman(&rest[..i], &inventories);
+            }
+
+            let c = rest[i..].chars().next().expect("found at `i`");
+            if is_devanāgarī(c) {
+                counts.devanāgarī += 1;
+            } else {
+                counts.gujarātī += 1;
+            }
+            rest = &rest[i + c.len_utf8()..];
+        }
+        if !rest.is_empty() {
+            counts.roman(rest, &inventories);
+        }
+    }
+
+    // only the letters of the schemes that are compiled in are weighed
+    if !cfg!(feature = "gu") {
+        counts.gujarātī = 0;
+    }
+    if !cfg!(feature = "iast") {
+        counts.roman = 0;
+    }
+
+    let total = counts.devanāgarī + counts.gujarātī + counts.roman;
+    if total == 0 {
+        return Vec::new();
+    }
+
+    #[cfg(feature = "iast")]
+    let iast_evidence = 1.0 + (counts.iast + counts.digraphs) as f32;
+    #[cfg_attr(not(feature = "slp"), allow(unused_mut))]
+    let mut roman = vec![
+        #[cfg(feature = "iast")]
+        (Scheme::Iast, score(iast_evidence, counts.unknown_iast)),
+        #[cfg(feature = "uast")]
+        (
+            // UAST-IO without escapes is IAST
+            Scheme::UastIo,
+            score(
+                3.0 * counts.escapes as f32 + iast_evidence / 2.0,
+                counts.unknown_uast,
+            ),
+        ),
+    ];
+    #[cfg(feature = "slp")]
+    {
+        let slp_evidence = 1.0 + counts.slp as f32;
+        roman.push((Scheme::Slp1, score(slp_evidence, counts.unknown_slp)));
+
+        // a lookalike that reads the text with as few unknown characters is at least as likely as
+        // SLP1. once capitals or letters IAST lacks show the text is not IAST, the aspirates and
+        // diphthongs count for the lookalikes that spell them with two letters.
+        roman.extend(
+            inventories
+                .lookalikes
+                .iter()
+                .zip(&counts.unknown_lookalikes)
+                .filter(|(_, n)| **n <= counts.unknown_slp)
+                .map(|((v, _, digraphs), &n)| {
+                    let evidence = if *digraphs && counts.slp > 0 {
+                        slp_evidence + counts.digraphs as f32
+                    } else {
+                        slp_evidence
+                    };
+                    (v.clone(), score(evidence, n))
+                }),
+        );
+    }
+    let sum = roman.iter().map(|v: &(Scheme, f32)| v.1).sum::<f32>();
+    let share = |n: usize| n as f32 / total as f32;
+
+    let mut res = [
+        (Scheme::Devanāgarī, share(counts.devanāgarī)),
+        #[cfg(feature = "gu")]
+        (Scheme::Gujarātī, share(counts.gujarātī)),
+    ]
+    .into_iter()
+    .chain(
+        roman
+            .into_iter()
+            .map(|(k, v)| (k, share(counts.roman) * v / sum)),
+    )
+    .filter(|v| v.1 > 0.0)
+    .map(|(scheme, confidence)| Detection { scheme, confidence })
+    .collect::<Vec<_>>();
+    res.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+
+    res
+}
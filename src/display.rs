@@ -0,0 +1,113 @@
+//! Text that is transliterated as it is formatted
+//!
+//! The wrappers write straight into the [`fmt::Formatter`], so `format!`, `write!` and template
+//! engines that take a [`Display`] do not need a `String` of the converted text first. The text is
+//! converted again every time it is formatted.
+//!
+//! ```
+//! use uast::display::{AsDevanāgarī, AsGujarātī, AsIast};
+//!
+//! let s = "bhargo devasya dhīmahi";
+//! assert_eq!("भर्गो देवस्य धीमहि", format!("{}", AsDevanāgarī(s)));
+//! assert_eq!("bhargo devasya", format!("{}", AsIast("भर्गो देवस्य")));
+//! assert_eq!("<p>ભર્ગો</p>", format!("<p>{}</p>", AsGujarātī("भर्गो")));
+//! ```
+
+use crate::{Options, Scheme, transliterate_into};
+use core::fmt::{self, Display};
+
+/// `text` converted from any [`Scheme`] to any other [`Scheme`] when it is formatted, like
+/// [`transliterate_with`](crate::transliterate_with).
+///
+/// The width, fill and alignment of the format string are not applied to the output.
+///
+/// ```
+/// use uast::{Options, Scheme, Unknown, display::Transliterated};
+///
+/// let v = Transliterated::new("Bargo devasya!", Scheme::Slp1, Scheme::Devanāgarī);
+/// assert_eq!("भर्गो देवस्य", v.to_string());
+///
+/// let v = v.with_options(Options {
+///     unknown: Unknown::PassThrough,
+///     ..Default::default()
+/// });
+/// assert_eq!("भर्गो देवस्य!", v.to_string());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Transliterated<'a> {
+    text: &'a str,
+    from: Scheme,
+    to: Scheme,
+    opts: Options,
+}
+
+impl<'a> Transliterated<'a> {
+    /// This function wraps `text` in `from` to be formatted in `to` with the default [`Options`].
+    pub fn new(text: &'a str, from: Scheme, to: Scheme) -> Self {
+        Self {
+            text,
+            from,
+            to,
+            opts: Options::default(),
+        }
+    }
+
+    /// This function replaces the [`Options`] the text is converted with.
+    pub fn with_options(self, opts: Options) -> Self {
+        Self { opts, ..self }
+    }
+}
+
+impl Display for Transliterated<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        transliterate_into(self.text, self.from.clone(), self.to.clone(), &self.opts, f)
+    }
+}
+
+/// IAST formatted in देवनागरी.
+#[cfg(feature = "iast")]
+#[derive(Debug, Clone, Copy)]
+pub struct AsDevanāgarī<'a>(pub &'a str);
+
+#[cfg(feature = "iast")]
+impl Display for AsDevanāgarī<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Transliterated::new(self.0, Scheme::Iast, Scheme::Devanāgarī).fmt(f)
+    }
+}
+
+/// देवनागरी formatted in IAST.
+#[cfg(feature = "iast")]
+#[derive(Debug, Clone, Copy)]
+pub struct AsIast<'a>(pub &'a str);
+
+#[cfg(feature = "iast")]
+impl Display for AsIast<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Transliterated::new(self.0, Scheme::Devanāgarī, Scheme::Iast).fmt(f)
+    }
+}
+
+/// देवनागरी formatted in ગુજરાતી.
+#[cfg(feature = "gu")]
+#[derive(Debug, Clone, Copy)]
+pub struct AsGujarātī<'a>(pub &'a str);
+
+#[cfg(feature = "gu")]
+impl Display for AsGujarātī<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Transliterated::new(self.0, Scheme::Devanāgarī, Scheme::Gujarātī).fmt(f)
+    }
+}
+
+/// देवनागरी formatted in SLP1.
+#[cfg(feature = "slp")]
+#[derive(Debug, Clone, Copy)]
+pub struct AsSlp1<'a>(pub &'a str);
+
+#[cfg(feature = "slp")]
+impl Display for AsSlp1<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Transliterated::new(self.0, Scheme::Devanāgarī, Scheme::Slp1).fmt(f)
+    }
+}
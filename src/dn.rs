@@ -0,0 +1,69 @@
+//! The देवनागरी letters every scheme is converted through
+
+use crate::{TokenKind, tables::Category};
+
+// ऎ and ऒ are the short e and o of the Dravidian languages, and ऍ and ऑ the open e and o of
+// English loanwords, which only ISO 15919 writes
+static VOWELS: [char; 18] = [
+    'अ', 'आ', 'इ', 'ई', 'उ', 'ऊ', 'ऋ', 'ऌ', 'ऍ', 'ऎ', 'ए', 'ऐ', 'ऑ', 'ऒ', 'ओ', 'औ', 'ॠ', 'ॡ',
+];
+
+static VOWEL_SIGNS: [char; 17] = [
+    'ा', 'ि', 'ी', 'ु', 'ू', 'ृ', 'ॄ', 'ॅ', 'ॆ', 'े', 'ै', 'ॉ', 'ॊ', 'ो', 'ौ', 'ॢ', 'ॣ',
+];
+
+// ऩ, ऱ, ऴ and the nukta letters from क़ on are only written by ISO 15919. the nukta letters stay
+// decomposed in NFC, so in text they are the letter without the nukta followed by it.
+static CONSONANTS: [char; 45] = [
+    'क', 'ख', 'ग', 'घ', 'ङ', 'च', 'छ', 'ज', 'झ', 'ञ', 'ट', 'ठ', 'ड', 'ढ', 'ण', 'त', 'थ', 'द', 'ध',
+    'न', 'ऩ', 'प', 'फ', 'ब', 'भ', 'म', 'य', 'र', 'ऱ', 'ल', 'ळ', 'ऴ', 'व', 'श', 'ष', 'स', 'ह',
+    '\u{958}', '\u{959}', '\u{95a}', '\u{95b}', '\u{95c}', '\u{95d}', '\u{95e}', '\u{95f}',
+];
+
+static MISC: [char; 14] = [
+    'ऽ', '।', '॥', '०', '१', '२', '३', '४', '५', '६', '७', '८', '९', 'ॱ',
+];
+
+// anusvāra, visarga, candrabindu, halanta, ॐ and ॰
+static SPECIALS: [char; 6] = ['ं', 'ः', 'ँ', '्', 'ॐ', '॰'];
+
+static VEDA_ACCENTS: [char; 14] = ['॑', '॒', '᳚', '꣠', '꣡', '꣢', '꣣', '꣤', '꣥', '꣦', '꣧', '꣨', '꣩', 'ꣳ'];
+
+pub(crate) fn is_veda_accent(c: char) -> bool {
+    VEDA_ACCENTS.binary_search(&c).is_ok()
+}
+
+/// Returns every देवनागरी letter, grouped by what it is in a syllable
+pub(crate) fn table() -> impl Iterator<Item = (Category, char)> {
+    let tag = |category| move |&c: &char| (category, c);
+
+    VOWELS
+        .iter()
+        .map(tag(Category::Vowel))
+        .chain(VOWEL_SIGNS.iter().map(tag(Category::VowelSign)))
+        .chain(CONSONANTS.iter().map(tag(Category::Consonant)))
+        .chain(MISC.iter().map(tag(Category::Misc)))
+        .chain(SPECIALS.iter().map(tag(Category::Special)))
+        .chain(VEDA_ACCENTS.iter().map(tag(Category::Special)))
+}
+
+/// Returns what the देवनागरी character `c` is in a syllable
+pub(crate) fn token_kind(c: char) -> TokenKind {
+    match c {
+        _ if VOWELS.binary_search(&c).is_ok() => TokenKind::Vowel,
+        _ if CONSONANTS.binary_search(&c).is_ok() => TokenKind::Consonant,
+        _ if VOWEL_SIGNS.binary_search(&c).is_ok() => TokenKind::VowelSign,
+        _ if is_veda_accent(c) => TokenKind::VedicAccent,
+        '्' => TokenKind::Halanta,
+        'ं' => TokenKind::Anusvāra,
+        'ः' => TokenKind::Visarga,
+        'ँ' => TokenKind::Candrabindu,
+        'ॐ' => TokenKind::Om,
+        '॰' | 'ॱ' => TokenKind::Abbreviation,
+        'ऽ' => TokenKind::Avagraha,
+        '।' => TokenKind::Daṇḍa,
+        '॥' => TokenKind::DoubleDaṇḍa,
+        '०'..='९' => TokenKind::Numeral,
+        _ => TokenKind::Other,
+    }
+}
@@ -0,0 +1,477 @@
+//! Table driven reading and writing of the Roman and Brahmic schemes
+
+use crate::{
+    Scheme, ScriptKind, TokenKind, dn, normalize,
+    tables::{Category, Entry},
+    utils::{self, Context},
+};
+use alloc::{string::String, vec::Vec};
+use core::{
+    fmt::{self, Write},
+    mem,
+    ops::Range,
+};
+
+// a character of the word once the escapes are resolved, along with the bytes it was read from
+#[derive(Clone, Copy)]
+pub(crate) struct Char {
+    pub(crate) c: char,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+// what a spelling stands for, depending on where it is found in a syllable
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub(crate) struct Letter {
+    pub(crate) vowel: Option<char>,
+    pub(crate) sign: Option<char>,
+    pub(crate) consonant: Option<char>,
+    pub(crate) other: Option<char>,
+}
+
+impl Letter {
+    // the slot a character of `category` goes in
+    pub(crate) fn slot(&mut self, category: Category, c: char) -> &mut Option<char> {
+        match category {
+            Category::Vowel => &mut self.vowel,
+            Category::VowelSign => &mut self.sign,
+            Category::Consonant => &mut self.consonant,
+            Category::Misc | Category::Special => &mut self.other,
+            Category::Escape => match dn::token_kind(c) {
+                TokenKind::Vowel => &mut self.vowel,
+                TokenKind::VowelSign => &mut self.sign,
+                TokenKind::Consonant => &mut self.consonant,
+                _ => &mut self.other,
+            },
+        }
+    }
+}
+
+/// The spellings of a scheme as they are read
+pub(crate) trait Parse {
+    /// The longest spelling at the start of `data`, along with its length in characters
+    fn longest_match(&self, data: &[Char]) -> Option<(usize, Letter)>;
+
+    /// Whether `c` stands for input that has already been recorded as unknown
+    fn recorded(&self, _c: &Char) -> bool {
+        false
+    }
+
+    /// The length in characters of a separator at the start of `data` that keeps the letters
+    /// `prev` before it apart from the ones after it
+    fn separator_at(&self, _prev: &[Char], _data: &[Char]) -> Option<usize> {
+        None
+    }
+}
+
+/// The spellings of a scheme as they are written
+pub(crate) trait Render {
+    /// The spelling that is written for the देवनागरी character `c`
+    fn spelling(&self, c: char) -> Option<(&str, Category)>;
+
+    /// The spelling of the inherent vowel
+    fn inherent(&self) -> &str;
+
+    /// Whether `c` has no spelling but is written as is all the same
+    fn copies(&self, _c: char) -> bool {
+        false
+    }
+
+    /// The text written between the spellings `prev` and `next` when they would otherwise be read
+    /// as one letter
+    fn separator(&self, _prev: &str, _next: &str) -> Option<&str> {
+        None
+    }
+}
+
+/// The letters of a [`SchemeDefinition`](crate::SchemeDefinition) arranged for lookups.
+///
+/// It is built from the entries and escapes of the scheme, and is best built once and kept along
+/// with them. [`CustomScheme`](crate::CustomScheme) builds it when it is loaded.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SchemeIndex {
+    kind: ScriptKind,
+    // sorted by spelling
+    letters: Vec<(String, Letter)>,
+    // length in characters of the longest spelling
+    longest: usize,
+    // the spelling that is written for every देवनागरी character, sorted by the character
+    texts: Vec<(char, String, Category)>,
+    inherent: String,
+    // longest first
+    escapes: Vec<(String, String)>,
+}
+
+impl SchemeIndex {
+    /// This function arranges the letters of a scheme of `kind` for lookups.
+    pub fn new(kind: ScriptKind, entries: &[Entry], escapes: &[(String, String)]) -> Self {
+        let mut letters = Vec::<(String, Letter)>::with_capacity(entries.len());
+        let mut texts = Vec::with_capacity(entries.len());
+
+        for v in entries {
+            let text = v.text.as_str();
+            let c = v.devanāgarī;
+            texts.push((c, text, v.category));
+
+            let i = match letters.iter().position(|l| l.0 == text) {
+                Some(i) => i,
+                None => {
+                    letters.push((text.into(), Letter::default()));
+                    letters.len() - 1
+                }
+            };
+            letters[i].1.slot(v.category, c).get_or_insert(c);
+        }
+
+        letters.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        let longest = letters
+            .iter()
+            .map(|v| v.0.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        // the first spelling of a character is the one that is written
+        texts.retain(|v| v.2 != Category::Escape);
+        texts.sort_by_key(|v| v.0);
+        texts.dedup_by_key(|v| v.0);
+
+        let inherent = texts
+            .binary_search_by_key(&'अ', |v| v.0)
+            .map_or("", |i| texts[i].1)
+            .into();
+
+        let mut escapes = escapes.to_vec();
+        escapes.sort_by_key(|v| core::cmp::Reverse(v.0.len()));
+
+        Self {
+            kind,
+            letters,
+            longest,
+            texts: texts
+                .into_iter()
+                .map(|(c, text, category)| (c, text.into(), category))
+                .collect(),
+            inherent,
+            escapes,
+        }
+    }
+}
+
+// the characters of `word` once the `escapes`, longest first, are replaced, in lowercase if asked
+pub(crate) fn resolve<K, V>(word: &str, escapes: &[(K, V)], lowercase: bool, arr: &mut Vec<Char>)
+where
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let mut i = 0;
+
+    while let Some(c) = word[i..].chars().next() {
+        if let Some((k, v)) = escapes.iter().find(|v| word[i..].starts_with(v.0.as_ref())) {
+            let (k, v) = (k.as_ref(), v.as_ref());
+            let end = i + k.len();
+            arr.extend(v.chars().map(|c| Char { c, start: i, end }));
+            i = end;
+            continue;
+        }
+
+        let end = i + c.len_utf8();
+        let c = if lowercase { utils::lowercase(c) } else { c };
+        arr.push(Char { c, start: i, end });
+        i = end;
+    }
+}
+
+impl Parse for SchemeIndex {
+    fn longest_match(&self, data: &[Char]) -> Option<(usize, Letter)> {
+        (1..=self.longest.min(data.len())).rev().find_map(|n| {
+            self.letters
+                .binary_search_by(|v| v.0.chars().cmp(data[..n].iter().map(|c| c.c)))
+                .ok()
+                .map(|i| (n, self.letters[i].1))
+        })
+    }
+}
+
+impl Render for SchemeIndex {
+    fn spelling(&self, c: char) -> Option<(&str, Category)> {
+        self.texts
+            .binary_search_by_key(&c, |v| v.0)
+            .ok()
+            .map(|i| (self.texts[i].1.as_str(), self.texts[i].2))
+    }
+
+    fn inherent(&self) -> &str {
+        &self.inherent
+    }
+}
+
+/// Reads the characters `data` of a word of a Roman scheme into देवनागरी
+pub(crate) fn read_roman(
+    word: &str,
+    data: &[Char],
+    index: &dyn Parse,
+    ctx: &mut Context,
+    out: &mut dyn Write,
+) -> fmt::Result {
+    // bytes of the word read for `data[i..j]`
+    let span = |i: usize, j: usize| data[i].start..data[j - 1].end;
+    let mut i = 0;
+
+    // here's a little thing about how saṃskṛta and devanāgarī work:
+    // the general formation of a syllable in saṃskṛta is `consonant + vowel_sign`.
+    // there is a designated symbol for each consonant and vowel_sign in devanāgarī.
+    // in the case where the syllable may start with a vowel, we put a special symbol instead of its sign.
+    // if we encounter a miscellaneous symbol, we put its designated symbol and start finding the consonant again.
+    // in the case that the `misc` symbol occurs in middle of a word like an avagraha, we first have to finish the
+    // preceding syllable. if that syllable was a running consonant, we add a halanta. we also add halanta
+    // at end of word to represent a consonant without a corresponding vowel with it.
+    while i < data.len() {
+        // a separator is read as nothing
+        if let Some(n) = index.separator_at(&data[..i], &data[i..]) {
+            i += n;
+            continue;
+        }
+
+        let Some((n, l)) = index.longest_match(&data[i..]) else {
+            if !index.recorded(&data[i]) {
+                ctx.unknown(word, span(i, i + 1));
+            }
+            ctx.pass(out, span(i, i + 1), &word[span(i, i + 1)])?;
+            i += 1;
+            continue;
+        };
+
+        let consonant = span(i, i + n);
+        i += n;
+
+        let Some(c) = l.consonant else {
+            if let Some(v) = l.vowel.or(l.other).or(l.sign) {
+                ctx.write_char(out, consonant, v)?;
+            }
+            continue;
+        };
+        write_letter(ctx, out, consonant.clone(), c)?;
+
+        match index.longest_match(&data[i..]) {
+            Some((n, v)) if v.vowel == Some('अ') => {
+                ctx.extend(data[i + n - 1].end);
+                i += n;
+            }
+            Some((n, Letter { sign: Some(v), .. }))
+            | Some((
+                n,
+                Letter {
+                    other: Some(v @ '्'),
+                    ..
+                },
+            )) => {
+                ctx.write_char(out, span(i, i + n), v)?;
+                i += n;
+            }
+            _ => ctx.write_char(out, consonant, '्')?,
+        }
+    }
+
+    Ok(())
+}
+
+// writes the देवनागरी letter `c` as the output of the bytes `range`, with the nukta of a nukta
+// letter apart as NFC has it
+fn write_letter(
+    ctx: &mut Context,
+    out: &mut dyn Write,
+    range: Range<usize>,
+    c: char,
+) -> fmt::Result {
+    match normalize::nukta_decomposition(c) {
+        Some((a, nukta)) => {
+            ctx.write_char(out, range.clone(), a)?;
+            ctx.write_char(out, range, nukta)
+        }
+        None => ctx.write_char(out, range, c),
+    }
+}
+
+fn read_brahmic(
+    word: &str,
+    data: &[Char],
+    index: &dyn Parse,
+    ctx: &mut Context,
+    out: &mut dyn Write,
+) -> fmt::Result {
+    let span = |i: usize, j: usize| data[i].start..data[j - 1].end;
+    let mut i = 0;
+
+    while i < data.len() {
+        match index.longest_match(&data[i..]) {
+            Some((n, l)) => {
+                if let Some(v) = l.consonant.or(l.vowel).or(l.sign).or(l.other) {
+                    write_letter(ctx, out, span(i, i + n), v)?;
+                }
+                i += n;
+            }
+            None => {
+                ctx.unknown(word, span(i, i + 1));
+                ctx.pass(out, span(i, i + 1), &word[span(i, i + 1)])?;
+                i += 1;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// the index of the custom scheme `scheme`, or the one built for it when it keeps none
+fn custom_index<'a>(scheme: &'a Scheme, built: Option<&'a SchemeIndex>) -> &'a SchemeIndex {
+    match scheme {
+        Scheme::Custom(v) => v.index().or(built),
+        _ => None,
+    }
+    .expect("the scheme is a custom scheme")
+}
+
+/// Reads a word of the custom source scheme into देवनागरी
+pub(crate) fn reader(word: &str, ctx: &mut Context, out: &mut dyn Write) -> fmt::Result {
+    let (scheme, built) = (ctx.from.clone(), ctx.source.take());
+    let index = custom_index(&scheme, built.as_ref());
+    let mut chars = mem::take(&mut ctx.chars);
+    chars.clear();
+
+    resolve(word, &index.escapes, false, &mut chars);
+    let res = match index.kind {
+        ScriptKind::Roman => read_roman(word, &chars, index, ctx, out),
+        ScriptKind::Brahmic => read_brahmic(word, &chars, index, ctx, out),
+    };
+
+    ctx.chars = chars;
+    ctx.source = built;
+    res
+}
+
+/// Writes a देवनागरी word in a Roman scheme
+pub(crate) fn write_roman(
+    dn: &str,
+    index: &dyn Render,
+    ctx: &mut Context,
+    out: &mut dyn Write,
+) -> fmt::Result {
+    let mut str = dn.char_indices().peekable();
+    // the spelling written last, which the next one may have to be kept apart from
+    let mut prev = "";
+
+    while let Some((i, c)) = str.next() {
+        let mut range = i..i + c.len_utf8();
+
+        // a letter followed by a nukta may have a spelling of its own, like क़ in ISO 15919
+        let c = match (str.peek(), normalize::nukta_letter(c)) {
+            (Some(&(j, '\u{93c}')), Some(v)) if index.spelling(v).is_some() => {
+                range.end = j + '\u{93c}'.len_utf8();
+                str.next();
+                v
+            }
+            _ => c,
+        };
+
+        // a vowel sign is only written after a consonant
+        let Some((text, category)) = index.spelling(c).filter(|v| v.1 != Category::VowelSign)
+        else {
+            prev = "";
+            if index.copies(c) {
+                ctx.copied(dn, range.clone());
+                ctx.write_char(out, range, c)?;
+                continue;
+            }
+
+            ctx.unknown(dn, range.clone());
+            ctx.pass(out, range.clone(), &dn[range])?;
+            continue;
+        };
+        write_apart(index, &mut prev, ctx, out, range.clone(), text)?;
+
+        // anything other than a halanta or a vowel sign ends the syllable with the inherent vowel
+        if category != Category::Consonant {
+            continue;
+        }
+
+        // a nukta the scheme cannot write is left out, and the vowel sign after it still belongs
+        // to the consonant
+        if let Some(&(j, v @ '\u{93c}')) = str.peek() {
+            if index.spelling(v).is_none() {
+                let nukta = j..j + v.len_utf8();
+                ctx.unknown(dn, nukta.clone());
+                ctx.pass(out, nukta.clone(), &dn[nukta])?;
+                str.next();
+            }
+        }
+
+        match str.peek() {
+            Some(&(j, '्')) => {
+                ctx.extend(j + '्'.len_utf8());
+                str.next();
+            }
+            Some(&(j, v)) => match index.spelling(v) {
+                Some((s, Category::VowelSign)) => {
+                    write_apart(index, &mut prev, ctx, out, j..j + v.len_utf8(), s)?;
+                    str.next();
+                }
+                // a vowel sign the scheme cannot write still takes the place of the inherent vowel
+                None if dn::token_kind(v) == TokenKind::VowelSign => (),
+                _ => write_apart(index, &mut prev, ctx, out, range, index.inherent())?,
+            },
+            None => write_apart(index, &mut prev, ctx, out, range, index.inherent())?,
+        }
+    }
+
+    Ok(())
+}
+
+// writes `text` after `prev`, with the separator of the scheme between them if it needs one
+fn write_apart<'a>(
+    index: &dyn Render,
+    prev: &mut &'a str,
+    ctx: &mut Context,
+    out: &mut dyn Write,
+    range: Range<usize>,
+    text: &'a str,
+) -> fmt::Result {
+    if let Some(v) = index.separator(prev, text) {
+        ctx.write_str(out, range.clone(), v)?;
+    }
+    *prev = text;
+    ctx.write_str(out, range, text)
+}
+
+fn write_brahmic(
+    dn: &str,
+    index: &dyn Render,
+    ctx: &mut Context,
+    out: &mut dyn Write,
+) -> fmt::Result {
+    for (i, c) in dn.char_indices() {
+        let range = i..i + c.len_utf8();
+
+        match index.spelling(c) {
+            Some((text, _)) => ctx.write_str(out, range, text)?,
+            None => {
+                ctx.unknown(dn, range.clone());
+                ctx.pass(out, range.clone(), &dn[range])?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes a देवनागरी word in the custom target scheme
+pub(crate) fn writer(dn: &str, ctx: &mut Context, out: &mut dyn Write) -> fmt::Result {
+    let (scheme, built) = (ctx.to.clone(), ctx.target.take());
+    let index = custom_index(&scheme, built.as_ref());
+
+    let res = match index.kind {
+        ScriptKind::Roman => write_roman(dn, index, ctx, out),
+        ScriptKind::Brahmic => write_brahmic(dn, index, ctx, out),
+    };
+
+    ctx.target = built;
+    res
+}
@@ -0,0 +1,137 @@
+//! Errors returned by the strict conversions and diagnostics reported by the others
+
+use crate::Scheme;
+use alloc::{format, string::String, vec::Vec};
+use core::{error::Error, fmt};
+
+/// A character or a sequence of characters of the input that could not be transliterated.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Unmappable {
+    /// Byte offset of `text` in the input.
+    pub byte_offset: usize,
+    /// Character offset of `text` in the input.
+    pub char_offset: usize,
+    /// The offending character, or the whole `/…/` sequence for an unknown UAST-IO escape.
+    pub text: String,
+}
+
+/// Error returned when some characters of the input could not be transliterated.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TransliterationError {
+    /// The scheme the input was read as.
+    pub scheme: Scheme,
+    /// Every unmappable character in the order it appears in the input.
+    pub unmappable: Vec<Unmappable>,
+}
+
+impl fmt::Display for TransliterationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "could not transliterate {}:", self.scheme)?;
+
+        for (i, v) in self.unmappable.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, " \"{}\" at {}", v.text, v.char_offset)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for TransliterationError {}
+
+/// Error returned when a [`Definition`](crate::Definition) cannot be loaded.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DefinitionError {
+    /// The TOML or JSON is malformed.
+    Syntax(String),
+    /// A key that is not a single देवनागरी character.
+    NotOneCharacter(String),
+    /// A spelling or an escape that is empty or has whitespace in it.
+    InvalidSpelling(String),
+    /// A Roman scheme without a spelling for अ, which consonants are written with.
+    MissingInherentVowel,
+}
+
+impl fmt::Display for DefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DefinitionError::Syntax(e) => write!(f, "invalid scheme definition: {e}"),
+            DefinitionError::NotOneCharacter(k) => {
+                write!(f, "\"{k}\" is not a single देवनागरी character")
+            }
+            DefinitionError::InvalidSpelling(v) => write!(f, "invalid spelling \"{v}\""),
+            DefinitionError::MissingInherentVowel => f.write_str("no spelling for अ"),
+        }
+    }
+}
+
+impl Error for DefinitionError {}
+
+/// What a [`Diagnostic`] is about.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DiagnosticKind {
+    /// A character that is not part of the source scheme.
+    Unknown(Scheme),
+    /// A `/…/` sequence that is not a UAST-IO escape.
+    UnknownEscape,
+    /// A देवनागरी character, like a Vedic accent, that the target scheme cannot write.
+    Unrepresentable(Scheme),
+    /// A देवनागरी character that the target scheme has no letters for and is written as is, like
+    /// ॐ in IAST.
+    CopiedAsIs(Scheme),
+}
+
+impl DiagnosticKind {
+    /// Whether the character is missing from the output, or copied through unchanged with
+    /// [`Unknown::PassThrough`](crate::Unknown::PassThrough).
+    pub fn is_lossy(&self) -> bool {
+        !matches!(self, DiagnosticKind::CopiedAsIs(_))
+    }
+}
+
+/// A spot in the input that was dropped or approximated during a conversion.
+///
+/// When a conversion goes through देवनागरी, what only the second converter can notice is reported
+/// at the start of the word it was found in.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    /// What happened.
+    pub kind: DiagnosticKind,
+    /// Byte offset of `text` in the input.
+    pub byte_offset: usize,
+    /// Character offset of `text` in the input.
+    pub char_offset: usize,
+    /// The character or the `/…/` sequence concerned.
+    pub text: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut chars = self.text.chars();
+        let text = match (chars.next(), chars.next()) {
+            (Some(c), None) => format!("\"{}\" (U+{:04X})", self.text, c as u32),
+            _ => format!("\"{}\"", self.text),
+        };
+
+        match &self.kind {
+            DiagnosticKind::Unknown(s) => write!(f, "unknown {s} character {text}")?,
+            DiagnosticKind::UnknownEscape => write!(f, "unknown UAST-IO escape {text}")?,
+            DiagnosticKind::Unrepresentable(s) => write!(f, "{text} cannot be written in {s}")?,
+            DiagnosticKind::CopiedAsIs(s) => write!(f, "{text} copied as is to {s}")?,
+        }
+
+        write!(f, " at offset {}", self.char_offset)
+    }
+}
+
+impl From<Diagnostic> for Unmappable {
+    fn from(v: Diagnostic) -> Self {
+        Self {
+            byte_offset: v.byte_offset,
+            char_offset: v.char_offset,
+            text: v.text,
+        }
+    }
+}
@@ -1,6 +1,11 @@
-//! देवनागरी to ગુજરાતી
+//! देवनागरी to ગુજરાતી and ગુજરાતી to देवनागरी
 
-use crate::utils::{binary_search, split_line_and_convert};
+use crate::{
+    Options, Scheme, TransliterationError, transliterate, transliterate_into, try_transliterate,
+    utils::{Context, binary_search},
+};
+use alloc::string::String;
+use core::fmt::{self, Write};
 
 static CHAR_DICT: [(char, char); 80] = [
     ('ँ', 'ઁ'),
@@ -85,12 +90,43 @@ static CHAR_DICT: [(char, char); 80] = [
     ('॰', '૰'),
 ];
 
-fn get_char(c: char) -> Option<char> {
+pub(crate) fn get_char(c: char) -> Option<char> {
     binary_search(&CHAR_DICT, c, |i| i)
 }
 
-fn convertor(dn: &str) -> String {
-    dn.chars().filter_map(get_char).collect()
+fn map_chars<F>(s: &str, ctx: &mut Context, out: &mut dyn Write, f: F) -> fmt::Result
+where
+    F: Fn(char) -> Option<char>,
+{
+    for (i, c) in s.char_indices() {
+        let range = i..i + c.len_utf8();
+
+        match f(c) {
+            Some(v) => ctx.write_char(out, range, v)?,
+            None => {
+                ctx.unknown(s, range.clone());
+                ctx.pass(out, range.clone(), &s[range])?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn convertor(dn: &str, ctx: &mut Context, out: &mut dyn Write) -> fmt::Result {
+    map_chars(dn, ctx, out, get_char)
+}
+
+pub(crate) fn get_devanāgarī(c: char) -> Option<char> {
+    CHAR_DICT.iter().find(|v| v.1 == c).map(|v| v.0)
+}
+
+pub(crate) fn gujarātī_convertor(
+    gu: &str,
+    ctx: &mut Context,
+    out: &mut dyn Write,
+) -> fmt::Result {
+    map_chars(gu, ctx, out, get_devanāgarī)
 }
 
 /// This function converts देवनागरी to ગુજરાતી.
@@ -105,5 +141,39 @@ fn convertor(dn: &str) -> String {
 /// );
 /// ```
 pub fn devanāgarī_to_gujarātī(dn: &str) -> String {
-    split_line_and_convert(convertor, dn)
+    transliterate(dn, Scheme::Devanāgarī, Scheme::Gujarātī)
+}
+
+/// This function converts देवनागरी to ગુજરાતી and fails on any character that is not देवनागरी.
+///
+/// ```
+/// use uast::try_devanāgarī_to_gujarātī;
+///
+/// assert_eq!("ભર્ગો", try_devanāgarī_to_gujarātī("भर्गो").unwrap());
+/// assert_eq!(1, try_devanāgarī_to_gujarātī("भर्गो!").unwrap_err().unmappable.len());
+/// ```
+pub fn try_devanāgarī_to_gujarātī(dn: &str) -> Result<String, TransliterationError> {
+    try_transliterate(dn, Scheme::Devanāgarī, Scheme::Gujarātī)
+}
+
+/// This function is [`devanāgarī_to_gujarātī`] that appends to `out` instead of allocating.
+///
+/// ```
+/// use uast::devanāgarī_to_gujarātī_into;
+///
+/// let mut s = String::from("૧. ");
+/// devanāgarī_to_gujarātī_into("नमः", &mut s).unwrap();
+/// assert_eq!("૧. નમઃ", s);
+/// ```
+pub fn devanāgarī_to_gujarātī_into<W>(dn: &str, out: &mut W) -> fmt::Result
+where
+    W: Write + ?Sized,
+{
+    transliterate_into(
+        dn,
+        Scheme::Devanāgarī,
+        Scheme::Gujarātī,
+        &Options::default(),
+        out,
+    )
 }
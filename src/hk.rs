@@ -0,0 +1,216 @@
+//! Harvard-Kyoto to देवनागरी and देवनागरी to Harvard-Kyoto
+
+use crate::{
+    Options, Scheme, TransliterationError,
+    roman::{Letters, Roman},
+    transliterate, transliterate_into, try_transliterate,
+    utils::Context,
+};
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+const LETTERS: Letters = Letters {
+    vowels: &[
+        ('अ', &["a"]),
+        ('आ', &["A"]),
+        ('इ', &["i"]),
+        ('ई', &["I"]),
+        ('उ', &["u"]),
+        ('ऊ', &["U"]),
+        ('ऋ', &["R"]),
+        ('ऌ', &["lR"]),
+        ('ए', &["e"]),
+        ('ऐ', &["ai"]),
+        ('ओ', &["o"]),
+        ('औ', &["au"]),
+        ('ॠ', &["RR"]),
+        ('ॡ', &["lRR"]),
+    ],
+    vowel_signs: &[
+        ('ा', &["A"]),
+        ('ि', &["i"]),
+        ('ी', &["I"]),
+        ('ु', &["u"]),
+        ('ू', &["U"]),
+        ('ृ', &["R"]),
+        ('ॄ', &["RR"]),
+        ('े', &["e"]),
+        ('ै', &["ai"]),
+        ('ो', &["o"]),
+        ('ौ', &["au"]),
+        ('ॢ', &["lR"]),
+        ('ॣ', &["lRR"]),
+    ],
+    consonants: &[
+        ('क', &["k"]),
+        ('ख', &["kh"]),
+        ('ग', &["g"]),
+        ('घ', &["gh"]),
+        ('ङ', &["G"]),
+        ('च', &["c"]),
+        ('छ', &["ch"]),
+        ('ज', &["j"]),
+        ('झ', &["jh"]),
+        ('ञ', &["J"]),
+        ('ट', &["T"]),
+        ('ठ', &["Th"]),
+        ('ड', &["D"]),
+        ('ढ', &["Dh"]),
+        ('ण', &["N"]),
+        ('त', &["t"]),
+        ('थ', &["th"]),
+        ('द', &["d"]),
+        ('ध', &["dh"]),
+        ('न', &["n"]),
+        ('प', &["p"]),
+        ('फ', &["ph"]),
+        ('ब', &["b"]),
+        ('भ', &["bh"]),
+        ('म', &["m"]),
+        ('य', &["y"]),
+        ('र', &["r"]),
+        ('ल', &["l"]),
+        ('ळ', &["L"]),
+        ('व', &["v"]),
+        ('श', &["z"]),
+        ('ष', &["S"]),
+        ('स', &["s"]),
+        ('ह', &["h"]),
+    ],
+    misc: &[
+        ('ँ', &["~"]),
+        ('ं', &["M"]),
+        ('ः', &["H"]),
+        ('ऽ', &["'"]),
+        ('।', &["|"]),
+        ('॥', &["||"]),
+        ('०', &["0"]),
+        ('१', &["1"]),
+        ('२', &["2"]),
+        ('३', &["3"]),
+        ('४', &["4"]),
+        ('५', &["5"]),
+        ('६', &["6"]),
+        ('७', &["7"]),
+        ('८', &["8"]),
+        ('९', &["9"]),
+    ],
+};
+
+pub(crate) static SCHEME: Roman = Roman {
+    letters: LETTERS,
+    readings: &LETTERS.readings::<{ LETTERS.count() }>(),
+    longest: LETTERS.longest(),
+    inherent: "a",
+    copies: &['ॐ', '॰'],
+    separator: None,
+    escapes: &[],
+    lowercase: false,
+};
+
+pub(crate) fn hk_convertor(hk: &str, ctx: &mut Context, out: &mut dyn Write) -> fmt::Result {
+    SCHEME.read(hk, ctx, out)
+}
+
+pub(crate) fn convertor(dn: &str, ctx: &mut Context, out: &mut dyn Write) -> fmt::Result {
+    SCHEME.write(dn, ctx, out)
+}
+
+/// This function converts Harvard-Kyoto to देवनागरी.
+///
+/// ```
+/// use uast::hk_to_devanāgarī;
+///
+/// let s = "bhUrbhuvaH svaH tatsaviturvareNyaM bhargo devasya dhImahi | dhiyo yo naH pracodayAt ||";
+/// assert_eq!(
+///     "भूर्भुवः स्वः तत्सवितुर्वरेण्यं भर्गो देवस्य धीमहि । धियो यो नः प्रचोदयात् ॥",
+///     hk_to_devanāgarī(&s)
+/// );
+/// ```
+pub fn hk_to_devanāgarī(hk: &str) -> String {
+    transliterate(hk, Scheme::HarvardKyoto, Scheme::Devanāgarī)
+}
+
+/// This function converts Harvard-Kyoto to देवनागरी and fails on any character that is not
+/// Harvard-Kyoto.
+///
+/// ```
+/// use uast::try_hk_to_devanāgarī;
+///
+/// assert_eq!("कॢप्तः", try_hk_to_devanāgarī("klRptaH").unwrap());
+/// assert_eq!("ā", try_hk_to_devanāgarī("rAma rāma").unwrap_err().unmappable[0].text);
+/// ```
+pub fn try_hk_to_devanāgarī(hk: &str) -> Result<String, TransliterationError> {
+    try_transliterate(hk, Scheme::HarvardKyoto, Scheme::Devanāgarī)
+}
+
+/// This function is [`hk_to_devanāgarī`] that appends to `out` instead of allocating.
+///
+/// ```
+/// use uast::hk_to_devanāgarī_into;
+///
+/// let mut s = String::from("१. ");
+/// hk_to_devanāgarī_into("zivaH", &mut s).unwrap();
+/// assert_eq!("१. शिवः", s);
+/// ```
+pub fn hk_to_devanāgarī_into<W>(hk: &str, out: &mut W) -> fmt::Result
+where
+    W: Write + ?Sized,
+{
+    transliterate_into(
+        hk,
+        Scheme::HarvardKyoto,
+        Scheme::Devanāgarī,
+        &Options::default(),
+        out,
+    )
+}
+
+/// This function converts देवनागरी to Harvard-Kyoto.
+///
+/// ```
+/// use uast::devanāgarī_to_hk;
+///
+/// assert_eq!(
+///     "maGgalaM bhagavAnviSNuH | kRSNaH ||",
+///     devanāgarī_to_hk("मङ्गलं भगवान्विष्णुः । कृष्णः ॥")
+/// );
+/// ```
+pub fn devanāgarī_to_hk(dn: &str) -> String {
+    transliterate(dn, Scheme::Devanāgarī, Scheme::HarvardKyoto)
+}
+
+/// This function converts देवनागरी to Harvard-Kyoto and fails on any character that
+/// Harvard-Kyoto cannot write.
+///
+/// ```
+/// use uast::try_devanāgarī_to_hk;
+///
+/// assert_eq!("klRptaH", try_devanāgarī_to_hk("कॢप्तः").unwrap());
+/// assert_eq!("꣠", try_devanāgarī_to_hk("अ꣠").unwrap_err().unmappable[0].text);
+/// ```
+pub fn try_devanāgarī_to_hk(dn: &str) -> Result<String, TransliterationError> {
+    try_transliterate(dn, Scheme::Devanāgarī, Scheme::HarvardKyoto)
+}
+
+/// This function is [`devanāgarī_to_hk`] that appends to `out` instead of allocating.
+///
+/// ```
+/// use uast::devanāgarī_to_hk_into;
+///
+/// let mut s = String::from("1. ");
+/// devanāgarī_to_hk_into("शिवः", &mut s).unwrap();
+/// assert_eq!("1. zivaH", s);
+/// ```
+pub fn devanāgarī_to_hk_into<W>(dn: &str, out: &mut W) -> fmt::Result
+where
+    W: Write + ?Sized,
+{
+    transliterate_into(
+        dn,
+        Scheme::Devanāgarī,
+        Scheme::HarvardKyoto,
+        &Options::default(),
+        out,
+    )
+}
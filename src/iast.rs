@@ -1,12 +1,19 @@
 //! देवनागरी to IAST
 
-use crate::utils::{binary_search, split_line_and_convert};
+use crate::{
+    Options, Scheme, TransliterationError, dn,
+    engine::{self, Render},
+    tables::Category,
+    transliterate, transliterate_into, try_transliterate,
+    utils::{Context, binary_search},
+};
+use alloc::string::String;
+use core::fmt::{self, Write};
 
 type T = (char, &'static str);
 
 struct ScriptSpecials {
     om: char,
-    halanta: char,
     visarga: char,
     anusvāra: char,
     candrabindu: char,
@@ -110,118 +117,62 @@ static CHAR_DICT: Script = Script {
         anusvāra: 'ं',
         visarga: 'ः',
         candrabindu: 'ँ',
-        halanta: '्',
         saṃkṣipta: '॰',
     },
 };
 
 impl Script {
-    fn get_vowel(&self, c: char) -> Option<String> {
-        binary_search(&self.vowels, c, |i| i.to_string())
+    fn get_vowel(&self, c: char) -> Option<&'static str> {
+        binary_search(&self.vowels, c, |i| i)
     }
 
-    fn get_misc(&self, c: char) -> Option<String> {
-        binary_search(&self.misc, c, |i| i.to_string())
+    fn get_misc(&self, c: char) -> Option<&'static str> {
+        binary_search(&self.misc, c, |i| i)
     }
 
-    fn get_vowelsign(&self, c: char) -> Option<String> {
-        binary_search(&self.vowel_signs, c, |i| i.to_string())
+    fn get_vowelsign(&self, c: char) -> Option<&'static str> {
+        binary_search(&self.vowel_signs, c, |i| i)
     }
 
-    fn get_consonant(&self, c: char) -> Option<String> {
-        binary_search(&self.consonants, c, |i| i.to_string())
+    fn get_consonant(&self, c: char) -> Option<&'static str> {
+        binary_search(&self.consonants, c, |i| i)
     }
+}
 
-    fn contains_consonant(&self, c: char) -> bool {
-        binary_search(&self.consonants, c, |i| i).is_some()
+impl Render for Script {
+    fn spelling(&self, c: char) -> Option<(&'static str, Category)> {
+        let s = &self.specials;
+
+        match c {
+            _ if c == s.anusvāra => Some(("ṃ", Category::Special)),
+            _ if c == s.visarga => Some(("ḥ", Category::Special)),
+            _ if c == s.candrabindu => Some(("ã", Category::Special)),
+            _ => self
+                .get_vowel(c)
+                .map(|v| (v, Category::Vowel))
+                .or_else(|| self.get_vowelsign(c).map(|v| (v, Category::VowelSign)))
+                .or_else(|| self.get_consonant(c).map(|v| (v, Category::Consonant)))
+                .or_else(|| self.get_misc(c).map(|v| (v, Category::Misc))),
+        }
     }
 
-    fn contains_misc(&self, c: char) -> bool {
-        binary_search(&self.misc, c, |i| i).is_some()
+    fn inherent(&self) -> &'static str {
+        "a"
     }
-}
-
-fn convertor(dn: &str) -> String {
-    let str = dn.to_lowercase().chars().collect::<Vec<char>>();
 
-    let mut arr = Vec::<String>::with_capacity(str.len());
-
-    let mut i = 0;
-
-    // if starts with vowel
-    if let Some(v) = CHAR_DICT.get_vowel(str[i]) {
-        arr.push(v);
-        i += 1;
+    // IAST has no letters for these, so they are written as is
+    fn copies(&self, c: char) -> bool {
+        c == self.specials.om || c == self.specials.saṃkṣipta
     }
+}
 
-    while i < str.len() {
-        if str[i] == CHAR_DICT.specials.om || str[i] == CHAR_DICT.specials.saṃkṣipta {
-            arr.push(str[i].to_string());
-            i += 1;
-            continue;
-        }
-
-        if let Some(v) = CHAR_DICT.get_misc(str[i]) {
-            arr.push(v);
-            i += 1;
-            continue;
-        }
-
-        if str[i] == CHAR_DICT.specials.anusvāra {
-            arr.push("ṃ".to_string());
-            i += 1;
-            continue;
-        }
-
-        if str[i] == CHAR_DICT.specials.visarga {
-            arr.push("ḥ".to_string());
-            i += 1;
-            continue;
-        }
-
-        if str[i] == CHAR_DICT.specials.candrabindu {
-            arr.push("ã".to_string());
-            i += 1;
-            continue;
-        }
-
-        if let Some(c) = CHAR_DICT.get_consonant(str[i]) {
-            arr.push(c);
-
-            if i + 1 == str.len() {
-                arr.push("a".to_string());
-                i += 1;
-                continue;
-            }
-
-            let v = str[i + 1];
-            if v == CHAR_DICT.specials.halanta {
-                i += 2;
-                continue;
-            }
-
-            if let Some(s) = CHAR_DICT.get_vowelsign(v) {
-                arr.push(s);
-                i += 2;
-                continue;
-            }
-
-            if CHAR_DICT.contains_consonant(v)
-                || CHAR_DICT.contains_misc(v)
-                || v == CHAR_DICT.specials.anusvāra
-                || v == CHAR_DICT.specials.visarga
-                || v == CHAR_DICT.specials.candrabindu
-            {
-                arr.push("a".to_string());
-                i += 1;
-                continue;
-            }
-        }
-
-        i += 1;
-    }
+/// Returns every देवनागरी letter along with its IAST letters, if IAST can write it
+pub(crate) fn table() -> impl Iterator<Item = (Category, char, Option<&'static str>)> {
+    dn::table().map(|(k, c)| (k, c, CHAR_DICT.spelling(c).map(|v| v.0)))
+}
 
-    arr.join("")
+pub(crate) fn convertor(dn: &str, ctx: &mut Context, out: &mut dyn Write) -> fmt::Result {
+    engine::write_roman(dn, &CHAR_DICT, ctx, out)
 }
 
 /// This function converts देवनागरी to IAST.
@@ -236,5 +187,39 @@ fn convertor(dn: &str) -> String {
 /// );
 /// ```
 pub fn devanāgarī_to_iast(dn: &str) -> String {
-    split_line_and_convert(convertor, dn)
+    transliterate(dn, Scheme::Devanāgarī, Scheme::Iast)
+}
+
+/// This function converts देवनागरी to IAST and fails on any character that is not देवनागरी.
+///
+/// ```
+/// use uast::try_devanāgarī_to_iast;
+///
+/// assert_eq!("bhargo", try_devanāgarī_to_iast("भर्गो").unwrap());
+/// assert_eq!("x", try_devanāgarī_to_iast("भर्गो x").unwrap_err().unmappable[0].text);
+/// ```
+pub fn try_devanāgarī_to_iast(dn: &str) -> Result<String, TransliterationError> {
+    try_transliterate(dn, Scheme::Devanāgarī, Scheme::Iast)
+}
+
+/// This function is [`devanāgarī_to_iast`] that appends to `out` instead of allocating.
+///
+/// ```
+/// use uast::devanāgarī_to_iast_into;
+///
+/// let mut s = String::from("1. ");
+/// devanāgarī_to_iast_into("नमः", &mut s).unwrap();
+/// assert_eq!("1. namaḥ", s);
+/// ```
+pub fn devanāgarī_to_iast_into<W>(dn: &str, out: &mut W) -> fmt::Result
+where
+    W: Write + ?Sized,
+{
+    transliterate_into(
+        dn,
+        Scheme::Devanāgarī,
+        Scheme::Iast,
+        &Options::default(),
+        out,
+    )
 }
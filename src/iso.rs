@@ -0,0 +1,243 @@
+//! ISO 15919 to देवनागरी and देवनागरी to ISO 15919
+
+use crate::{
+    Options, Scheme, TransliterationError,
+    roman::{Letters, Roman},
+    transliterate, transliterate_into, try_transliterate,
+    utils::Context,
+};
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+// the vocalic r and l take a combining ring below, and a macron after it when they are long. the
+// letters of the other Indic languages, like the nukta letters, are written as well.
+const LETTERS: Letters = Letters {
+    vowels: &[
+        ('अ', &["a"]),
+        ('आ', &["ā"]),
+        ('इ', &["i"]),
+        ('ई', &["ī"]),
+        ('उ', &["u"]),
+        ('ऊ', &["ū"]),
+        ('ऋ', &["r\u{325}"]),
+        ('ऌ', &["l\u{325}"]),
+        ('ऍ', &["ê"]),
+        ('ऎ', &["e"]),
+        ('ए', &["ē"]),
+        ('ऐ', &["ai"]),
+        ('ऑ', &["ô"]),
+        ('ऒ', &["o"]),
+        ('ओ', &["ō"]),
+        ('औ', &["au"]),
+        ('ॠ', &["r\u{325}\u{304}"]),
+        ('ॡ', &["l\u{325}\u{304}"]),
+    ],
+    vowel_signs: &[
+        ('ा', &["ā"]),
+        ('ि', &["i"]),
+        ('ी', &["ī"]),
+        ('ु', &["u"]),
+        ('ू', &["ū"]),
+        ('ृ', &["r\u{325}"]),
+        ('ॄ', &["r\u{325}\u{304}"]),
+        ('ॅ', &["ê"]),
+        ('ॆ', &["e"]),
+        ('े', &["ē"]),
+        ('ै', &["ai"]),
+        ('ॉ', &["ô"]),
+        ('ॊ', &["o"]),
+        ('ो', &["ō"]),
+        ('ौ', &["au"]),
+        ('ॢ', &["l\u{325}"]),
+        ('ॣ', &["l\u{325}\u{304}"]),
+    ],
+    consonants: &[
+        ('क', &["k"]),
+        ('ख', &["kh"]),
+        ('ग', &["g"]),
+        ('घ', &["gh"]),
+        ('ङ', &["ṅ"]),
+        ('च', &["c"]),
+        ('छ', &["ch"]),
+        ('ज', &["j"]),
+        ('झ', &["jh"]),
+        ('ञ', &["ñ"]),
+        ('ट', &["ṭ"]),
+        ('ठ', &["ṭh"]),
+        ('ड', &["ḍ"]),
+        ('ढ', &["ḍh"]),
+        ('ण', &["ṇ"]),
+        ('त', &["t"]),
+        ('थ', &["th"]),
+        ('द', &["d"]),
+        ('ध', &["dh"]),
+        ('न', &["n"]),
+        ('ऩ', &["ṉ"]),
+        ('प', &["p"]),
+        ('फ', &["ph"]),
+        ('ब', &["b"]),
+        ('भ', &["bh"]),
+        ('म', &["m"]),
+        ('य', &["y"]),
+        ('र', &["r"]),
+        ('ऱ', &["ṟ"]),
+        ('ल', &["l"]),
+        ('ळ', &["ḷ"]),
+        ('ऴ', &["ḻ"]),
+        ('व', &["v"]),
+        ('श', &["ś"]),
+        ('ष', &["ṣ"]),
+        ('स', &["s"]),
+        ('ह', &["h"]),
+        // क़, ख़, ग़, ज़, ड़, ढ़, फ़ and य़
+        ('\u{958}', &["q"]),
+        ('\u{959}', &["ḵẖ"]),
+        ('\u{95a}', &["ġ"]),
+        ('\u{95b}', &["z"]),
+        ('\u{95c}', &["ṛ"]),
+        ('\u{95d}', &["ṛh"]),
+        ('\u{95e}', &["f"]),
+        ('\u{95f}', &["ẏ"]),
+    ],
+    // `ṃ` is the anusvāra of IAST, which is read all the same
+    misc: &[
+        ('ँ', &["m\u{310}"]),
+        ('ं', &["ṁ", "ṃ"]),
+        ('ः', &["ḥ"]),
+        ('ऽ', &["’", "'"]),
+        ('।', &["."]),
+        ('॥', &[".."]),
+        ('०', &["0"]),
+        ('१', &["1"]),
+        ('२', &["2"]),
+        ('३', &["3"]),
+        ('४', &["4"]),
+        ('५', &["5"]),
+        ('६', &["6"]),
+        ('७', &["7"]),
+        ('८', &["8"]),
+        ('९', &["9"]),
+    ],
+};
+
+pub(crate) static SCHEME: Roman = Roman {
+    letters: LETTERS,
+    readings: &LETTERS.readings::<{ LETTERS.count() }>(),
+    longest: LETTERS.longest(),
+    inherent: "a",
+    copies: &['ॐ', '॰'],
+    separator: Some(":"),
+    escapes: &[],
+    // capitals are only capitalisation
+    lowercase: true,
+};
+
+pub(crate) fn iso_convertor(iso: &str, ctx: &mut Context, out: &mut dyn Write) -> fmt::Result {
+    SCHEME.read(iso, ctx, out)
+}
+
+pub(crate) fn convertor(dn: &str, ctx: &mut Context, out: &mut dyn Write) -> fmt::Result {
+    SCHEME.write(dn, ctx, out)
+}
+
+/// This function converts ISO 15919 to देवनागरी.
+///
+/// `ṃ`, the anusvāra of IAST, is read as well as `ṁ`, and capitals are read as small letters.
+///
+/// ```
+/// use uast::iso_to_devanāgarī;
+///
+/// assert_eq!("कृष्णः देवं", iso_to_devanāgarī("kr̥ṣṇaḥ dēvaṁ"));
+/// assert_eq!("अइ कउत्", iso_to_devanāgarī("a:i ka:ut"));
+/// ```
+pub fn iso_to_devanāgarī(iso: &str) -> String {
+    transliterate(iso, Scheme::Iso15919, Scheme::Devanāgarī)
+}
+
+/// This function converts ISO 15919 to देवनागरी and fails on any character that is not
+/// ISO 15919.
+///
+/// ```
+/// use uast::try_iso_to_devanāgarī;
+///
+/// assert_eq!("कॢप्तः", try_iso_to_devanāgarī("kl̥ptaḥ").unwrap());
+/// assert_eq!("x", try_iso_to_devanāgarī("śiva x").unwrap_err().unmappable[0].text);
+/// ```
+pub fn try_iso_to_devanāgarī(iso: &str) -> Result<String, TransliterationError> {
+    try_transliterate(iso, Scheme::Iso15919, Scheme::Devanāgarī)
+}
+
+/// This function is [`iso_to_devanāgarī`] that appends to `out` instead of allocating.
+///
+/// ```
+/// use uast::iso_to_devanāgarī_into;
+///
+/// let mut s = String::from("१. ");
+/// iso_to_devanāgarī_into("śivaḥ", &mut s).unwrap();
+/// assert_eq!("१. शिवः", s);
+/// ```
+pub fn iso_to_devanāgarī_into<W>(iso: &str, out: &mut W) -> fmt::Result
+where
+    W: Write + ?Sized,
+{
+    transliterate_into(
+        iso,
+        Scheme::Iso15919,
+        Scheme::Devanāgarī,
+        &Options::default(),
+        out,
+    )
+}
+
+/// This function converts देवनागरी to ISO 15919.
+///
+/// Unlike IAST, the long ए and ओ are `ē` and `ō`, the anusvāra is `ṁ` and the vocalic r and l
+/// take a ring below. A `:` keeps apart vowels and consonants that would otherwise be read as a
+/// diphthong or an aspirate. Anywhere else it is punctuation, which ISO 15919 does not read. The
+/// letters of the other Indic languages, like क़, ऱ and ऑ, are written as well.
+///
+/// ```
+/// use uast::devanāgarī_to_iso;
+///
+/// assert_eq!("kr̥ṣṇaḥ dēvaṁ", devanāgarī_to_iso("कृष्णः देवं"));
+/// assert_eq!("qilā ṟa ḍôkṭar", devanāgarī_to_iso("क़िला ऱ डॉक्टर्"));
+/// assert_eq!("a:iti ka:ut ak:har", devanāgarī_to_iso("अइति कउत् अक्हर्"));
+/// ```
+pub fn devanāgarī_to_iso(dn: &str) -> String {
+    transliterate(dn, Scheme::Devanāgarī, Scheme::Iso15919)
+}
+
+/// This function converts देवनागरी to ISO 15919 and fails on any character that ISO 15919 cannot
+/// write.
+///
+/// ```
+/// use uast::try_devanāgarī_to_iso;
+///
+/// assert_eq!("kl̥ptaḥ", try_devanāgarī_to_iso("कॢप्तः").unwrap());
+/// assert_eq!("꣠", try_devanāgarī_to_iso("अ꣠").unwrap_err().unmappable[0].text);
+/// ```
+pub fn try_devanāgarī_to_iso(dn: &str) -> Result<String, TransliterationError> {
+    try_transliterate(dn, Scheme::Devanāgarī, Scheme::Iso15919)
+}
+
+/// This function is [`devanāgarī_to_iso`] that appends to `out` instead of allocating.
+///
+/// ```
+/// use uast::devanāgarī_to_iso_into;
+///
+/// let mut s = String::from("1. ");
+/// devanāgarī_to_iso_into("शिवः", &mut s).unwrap();
+/// assert_eq!("1. śivaḥ", s);
+/// ```
+pub fn devanāgarī_to_iso_into<W>(dn: &str, out: &mut W) -> fmt::Result
+where
+    W: Write + ?Sized,
+{
+    transliterate_into(
+        dn,
+        Scheme::Devanāgarī,
+        Scheme::Iso15919,
+        &Options::default(),
+        out,
+    )
+}
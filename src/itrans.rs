@@ -0,0 +1,179 @@
+//! ITRANS to देवनागरी and देवनागरी to ITRANS
+
+use crate::{
+    Options, Scheme, TransliterationError,
+    roman::{Letters, Roman},
+    transliterate, transliterate_into, try_transliterate,
+    utils::Context,
+};
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+const LETTERS: Letters = Letters {
+    vowels: &[
+        ('अ', &["a"]),
+        ('आ', &["A", "aa"]),
+        ('इ', &["i"]),
+        ('ई', &["I", "ii", "ee"]),
+        ('उ', &["u"]),
+        ('ऊ', &["U", "uu", "oo"]),
+        ('ऋ', &["RRi", "R^i"]),
+        ('ऌ', &["LLi", "L^i"]),
+        ('ए', &["e"]),
+        ('ऐ', &["ai"]),
+        ('ओ', &["o"]),
+        ('औ', &["au"]),
+        ('ॠ', &["RRI", "R^I"]),
+        ('ॡ', &["LLI", "L^I"]),
+    ],
+    vowel_signs: &[
+        ('ा', &["A", "aa"]),
+        ('ि', &["i"]),
+        ('ी', &["I", "ii", "ee"]),
+        ('ु', &["u"]),
+        ('ू', &["U", "uu", "oo"]),
+        ('ृ', &["RRi", "R^i"]),
+        ('ॄ', &["RRI", "R^I"]),
+        ('े', &["e"]),
+        ('ै', &["ai"]),
+        ('ो', &["o"]),
+        ('ौ', &["au"]),
+        ('ॢ', &["LLi", "L^i"]),
+        ('ॣ', &["LLI", "L^I"]),
+    ],
+    consonants: &[
+        ('क', &["k"]),
+        ('ख', &["kh"]),
+        ('ग', &["g"]),
+        ('घ', &["gh"]),
+        ('ङ', &["~N", "N^"]),
+        ('च', &["ch", "c"]),
+        ('छ', &["Ch", "chh"]),
+        ('ज', &["j"]),
+        ('झ', &["jh"]),
+        ('ञ', &["~n", "JN"]),
+        ('ट', &["T"]),
+        ('ठ', &["Th"]),
+        ('ड', &["D"]),
+        ('ढ', &["Dh"]),
+        ('ण', &["N"]),
+        ('त', &["t"]),
+        ('थ', &["th"]),
+        ('द', &["d"]),
+        ('ध', &["dh"]),
+        ('न', &["n"]),
+        ('प', &["p"]),
+        ('फ', &["ph"]),
+        ('ब', &["b"]),
+        ('भ', &["bh"]),
+        ('म', &["m"]),
+        ('य', &["y"]),
+        ('र', &["r"]),
+        ('ल', &["l"]),
+        ('ळ', &["L", "ld"]),
+        ('व', &["v", "w"]),
+        ('श', &["sh"]),
+        ('ष', &["Sh", "shh", "S"]),
+        ('स', &["s"]),
+        ('ह', &["h"]),
+    ],
+    misc: &[
+        ('ँ', &[".N"]),
+        ('ं', &["M", ".n", ".m"]),
+        ('ः', &["H"]),
+        ('ऽ', &[".a"]),
+        ('्', &[".h"]),
+        ('ॐ', &["OM", "AUM"]),
+        ('।', &["|"]),
+        ('॥', &["||"]),
+        ('०', &["0"]),
+        ('१', &["1"]),
+        ('२', &["2"]),
+        ('३', &["3"]),
+        ('४', &["4"]),
+        ('५', &["5"]),
+        ('६', &["6"]),
+        ('७', &["7"]),
+        ('८', &["8"]),
+        ('९', &["9"]),
+    ],
+};
+
+pub(crate) static SCHEME: Roman = Roman {
+    letters: LETTERS,
+    readings: &LETTERS.readings::<{ LETTERS.count() }>(),
+    longest: LETTERS.longest(),
+    inherent: "a",
+    copies: &['॰'],
+    separator: Some("{}"),
+    // the conjuncts with spellings of their own, read as the letters they are made of
+    escapes: &[("dny", "j~n"), ("GY", "j~n"), ("x", "kSh")],
+    lowercase: false,
+};
+
+pub(crate) fn itrans_convertor(
+    itrans: &str,
+    ctx: &mut Context,
+    out: &mut dyn Write,
+) -> fmt::Result {
+    SCHEME.read(itrans, ctx, out)
+}
+
+pub(crate) fn convertor(dn: &str, ctx: &mut Context, out: &mut dyn Write) -> fmt::Result {
+    SCHEME.write(dn, ctx, out)
+}
+
+/// This function converts ITRANS to देवनागरी.
+///
+/// Every spelling ITRANS allows for a letter is read, so `A` and `aa`, `RRi` and `R^i`, `~N` and
+/// `N^`, or `M` and `.n` give the same देवनागरी. `.h` is a halanta, `.a` an avagraha and `{}`
+/// keeps apart letters that would otherwise be read as one.
+///
+/// ```
+/// use uast::itrans_to_devanāgarī;
+///
+/// let s = "bhUrbhuvaH svaH tatsaviturvareNyaM bhargo devasya dhImahi | dhiyo yo naH prachodayAt ||";
+/// assert_eq!(
+///     "भूर्भुवः स्वः तत्सवितुर्वरेण्यं भर्गो देवस्य धीमहि । धियो यो नः प्रचोदयात् ॥",
+///     itrans_to_devanāgarī(&s)
+/// );
+/// assert_eq!(itrans_to_devanāgarī("kRRiShNa"), itrans_to_devanāgarī("kR^iShNa"));
+/// assert_eq!("अइ ऐ", itrans_to_devanāgarī("a{}i ai"));
+/// ```
+pub fn itrans_to_devanāgarī(itrans: &str) -> String {
+    transliterate(itrans, Scheme::Itrans, Scheme::Devanāgarī)
+}
+
+/// This function converts ITRANS to देवनागरी and fails on any character that is not ITRANS.
+///
+/// ```
+/// use uast::try_itrans_to_devanāgarī;
+///
+/// assert_eq!("ज्ञानम्", try_itrans_to_devanāgarī("GYAnam").unwrap());
+/// assert_eq!("ā", try_itrans_to_devanāgarī("rAma rāma").unwrap_err().unmappable[0].text);
+/// ```
+pub fn try_itrans_to_devanāgarī(itrans: &str) -> Result<String, TransliterationError> {
+    try_transliterate(itrans, Scheme::Itrans, Scheme::Devanāgarī)
+}
+
+/// This function is [`itrans_to_devanāgarī`] that appends to `out` instead of allocating.
+///
+/// ```
+/// use uast::itrans_to_devanāgarī_into;
+///
+/// let mut s = String::from("१. ");
+/// itrans_to_devanāgarī_into("shivaH", &mut s).unwrap();
+/// assert_eq!("१. शिवः", s);
+/// ```
+pub fn itrans_to_devanāgarī_into<W>(itrans: &str, out: &mut W) -> fmt::Result
+where
+    W: Write + ?Sized,
+{
+    transliterate_into(
+        itrans,
+        Scheme::Itrans,
+        Scheme::Devanāgarī,
+        &Options::default(),
+        out,
+    )
+}
@@ -4,19 +4,127 @@
 //! - देवनागरी: <https://www.unicode.org/charts/PDF/U0900.pdf>
 //! - ગુજરાતી: <https://www.unicode.org/charts/PDF/U0A80.pdf>
 //!
-//! <div class="warning">
-//! No Unicode normalization is performed. It is assumed that the text is already normalized.
-//! </div>
+//! The library only needs `alloc` when the default `std` feature is disabled, which leaves out
+//! [`transliterate_stream`]. Everything else, including the `*_into` functions that write to any
+//! [`core::fmt::Write`], is available under `#![no_std]`.
+//!
+//! Schemes the crate does not ship can be loaded at runtime as a [`CustomScheme`]. The `toml` and
+//! `json` features read their [`Definition`] from a file. Other crates can plug in their own
+//! scripts by implementing [`SchemeDefinition`].
+//!
+//! Every scheme other than देवनागरी sits behind a feature of the same name, all of them enabled by
+//! default: `iast`, `uast` for UAST-IO, `gu` for ગુજરાતી, `slp` for SLP1, `hk` for
+//! Harvard-Kyoto, `itrans` for ITRANS, `velthuis` for Velthuis, `iso` for ISO 15919 and `wx` for
+//! WX. UAST-IO is read along with IAST and SLP1 comes with functions to IAST, so they enable
+//! `iast` as well. Without a feature, its [`Scheme`] variant and functions are left out along
+//! with its tables.
+//!
+//! Input is normalized to NFC before it is read, so decomposed letters, like `a` followed by a
+//! combining macron in IAST copied from a PDF, read the same as composed ones. The output is in
+//! NFC unless [`Options::normalization`] asks for NFD.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+#[cfg(all(test, not(feature = "std")))]
+extern crate std;
 
+mod chars;
+mod custom;
+mod detect;
+pub mod display;
+mod dn;
+mod engine;
+mod error;
+#[cfg(feature = "gu")]
 mod gu;
+#[cfg(feature = "hk")]
+mod hk;
+#[cfg(feature = "iast")]
 mod iast;
+#[cfg(feature = "iso")]
+mod iso;
+#[cfg(feature = "itrans")]
+mod itrans;
+mod normalize;
+#[cfg(any(
+    feature = "slp",
+    feature = "hk",
+    feature = "itrans",
+    feature = "velthuis",
+    feature = "iso",
+    feature = "wx"
+))]
+mod roman;
+mod scheme;
+mod segment;
+#[cfg(feature = "slp")]
 mod slp;
+#[cfg(feature = "std")]
+mod stream;
+pub mod tables;
 #[cfg(test)]
 mod tests;
+pub mod text;
+mod token;
+// IAST is read by the UAST-IO reader, `/…/` escapes included
+#[cfg(feature = "iast")]
 mod uast;
 mod utils;
+#[cfg(feature = "velthuis")]
+mod velthuis;
+#[cfg(feature = "wx")]
+mod wx;
 
-pub use crate::gu::devanāgarī_to_gujarātī;
-pub use crate::iast::devanāgarī_to_iast;
-pub use crate::slp::slp_to_iast;
-pub use crate::uast::uast_to_devanāgarī;
+pub use crate::chars::{Transliterate, TransliterateIter, TransliteratedChars};
+pub use crate::custom::{CustomScheme, Definition, SchemeDefinition, ScriptKind, Spelling};
+pub use crate::detect::{Detection, detect_scheme};
+pub use crate::engine::SchemeIndex;
+pub use crate::error::{
+    DefinitionError, Diagnostic, DiagnosticKind, TransliterationError, Unmappable,
+};
+#[cfg(feature = "gu")]
+pub use crate::gu::{
+    devanāgarī_to_gujarātī, devanāgarī_to_gujarātī_into, try_devanāgarī_to_gujarātī,
+};
+#[cfg(feature = "hk")]
+pub use crate::hk::{
+    devanāgarī_to_hk, devanāgarī_to_hk_into, hk_to_devanāgarī, hk_to_devanāgarī_into,
+    try_devanāgarī_to_hk, try_hk_to_devanāgarī,
+};
+#[cfg(feature = "iast")]
+pub use crate::iast::{devanāgarī_to_iast, devanāgarī_to_iast_into, try_devanāgarī_to_iast};
+#[cfg(feature = "iso")]
+pub use crate::iso::{
+    devanāgarī_to_iso, devanāgarī_to_iso_into, iso_to_devanāgarī, iso_to_devanāgarī_into,
+    try_devanāgarī_to_iso, try_iso_to_devanāgarī,
+};
+#[cfg(feature = "itrans")]
+pub use crate::itrans::{
+    itrans_to_devanāgarī, itrans_to_devanāgarī_into, try_itrans_to_devanāgarī,
+};
+pub use crate::scheme::{
+    Aligned, Alignment, Converted, Normalization, Options, Scheme, Unknown, transliterate,
+    transliterate_into, transliterate_with, transliterate_with_alignment,
+    transliterate_with_diagnostics, try_transliterate,
+};
+pub use crate::segment::{Script, Segment, segment, transliterate_segments};
+#[cfg(feature = "slp")]
+pub use crate::slp::{slp_to_iast, slp_to_iast_into, try_slp_to_iast};
+#[cfg(feature = "std")]
+pub use crate::stream::transliterate_stream;
+#[cfg(feature = "uast")]
+pub use crate::token::tokenize_uast;
+pub use crate::token::{Token, TokenKind, tokenize_devanāgarī};
+#[cfg(feature = "uast")]
+pub use crate::uast::{try_uast_to_devanāgarī, uast_to_devanāgarī, uast_to_devanāgarī_into};
+#[cfg(feature = "velthuis")]
+pub use crate::velthuis::{
+    devanāgarī_to_velthuis, devanāgarī_to_velthuis_into, try_devanāgarī_to_velthuis,
+    try_velthuis_to_devanāgarī, velthuis_to_devanāgarī, velthuis_to_devanāgarī_into,
+};
+#[cfg(feature = "wx")]
+pub use crate::wx::{
+    devanāgarī_to_wx, devanāgarī_to_wx_into, try_devanāgarī_to_wx, try_wx_to_devanāgarī,
+    wx_to_devanāgarī, wx_to_devanāgarī_into,
+};
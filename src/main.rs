@@ -5,24 +5,67 @@ use std::{
         self,
         consts::{ARCH, OS},
     },
-    io::{self, Error, ErrorKind},
+    io::{self, Error, ErrorKind, Write},
 };
 use uast::*;
 
+const USAGE: &str = "Usage: uast [d|i|g|s|h|t|v|o|w|a]";
+
+// detections closer than this to the likeliest one are as good a guess
+const MARGIN: f32 = 0.1;
+
+// the whole input is read to detect its scheme. Indic scripts are romanised and the rest is
+// written in देवनागरी. when schemes that are about as likely read the text differently, it is
+// refused rather than guessed.
+fn auto() -> io::Result<()> {
+    let text = io::read_to_string(io::stdin().lock())?;
+    let res = detect_scheme(&text);
+    let from = res.first().map_or(Scheme::UastIo, |v| v.scheme.clone());
+    let target = |from: &Scheme| match from {
+        Scheme::Devanāgarī | Scheme::Gujarātī => Scheme::Iast,
+        _ => Scheme::Devanāgarī,
+    };
+    let to = target(&from);
+
+    let s = transliterate(&text, from.clone(), to);
+    for v in res.iter().skip(1) {
+        if res[0].confidence - v.confidence >= MARGIN {
+            break;
+        }
+        if transliterate(&text, v.scheme.clone(), target(&v.scheme)) != s {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "The text could be {from} or {}. Name its scheme instead. {USAGE}",
+                    v.scheme
+                ),
+            ));
+        }
+    }
+
+    io::stdout().lock().write_all(s.as_bytes())
+}
+
 fn main() -> io::Result<()> {
     let mut args = env::args();
     if args.len() > 2 {
         return Err(Error::new(
             ErrorKind::InvalidInput,
-            "Invalid number of arguments. Usage: uast [d|i|h|g|s]",
+            format!("Invalid number of arguments. {USAGE}"),
         ));
     }
 
-    let f: fn(&str) -> String = match args.nth(1).unwrap_or_else(|| "d".to_string()).as_str() {
-        "d" => uast_to_devanāgarī,
-        "i" => devanāgarī_to_iast,
-        "g" => devanāgarī_to_gujarātī,
-        "s" => slp_to_iast,
+    let (from, to) = match args.nth(1).unwrap_or_else(|| "d".to_string()).as_str() {
+        "d" => (Scheme::UastIo, Scheme::Devanāgarī),
+        "i" => (Scheme::Devanāgarī, Scheme::Iast),
+        "g" => (Scheme::Devanāgarī, Scheme::Gujarātī),
+        "s" => (Scheme::Slp1, Scheme::Iast),
+        "h" => (Scheme::HarvardKyoto, Scheme::Devanāgarī),
+        "t" => (Scheme::Itrans, Scheme::Devanāgarī),
+        "v" => (Scheme::Velthuis, Scheme::Devanāgarī),
+        "o" => (Scheme::Devanāgarī, Scheme::Iso15919),
+        "w" => (Scheme::Devanāgarī, Scheme::Wx),
+        "a" => return auto(),
 
         "-v" | "--version" => {
             #[cfg(debug_assertions)]
@@ -43,30 +86,20 @@ fn main() -> io::Result<()> {
             return Ok(());
         }
         "-h" | "--help" => {
-            println!("Usage: uast [d|i|g|s]");
+            println!("{USAGE}");
 
             return Ok(());
         }
         _ => {
-            return Err(Error::new(ErrorKind::InvalidInput, "Usage: uast [d|i|g|s]"));
+            return Err(Error::new(ErrorKind::InvalidInput, USAGE));
         }
     };
 
-    loop {
-        let mut l = String::new();
-
-        match io::stdin().read_line(&mut l) {
-            Err(e) => {
-                return Err(e);
-            }
-
-            Ok(0) => {
-                return Ok(());
-            }
-
-            _ => {
-                println!("{}", f(&l));
-            }
-        };
-    }
+    transliterate_stream(
+        io::stdin().lock(),
+        io::stdout().lock(),
+        from,
+        to,
+        &Options::default(),
+    )
 }
@@ -0,0 +1,224 @@
+//! Unicode normalization of the letters used by the schemes
+
+use crate::utils::binary_search;
+use alloc::{string::String, vec::Vec};
+use core::{
+    fmt::{self, Write},
+    ops::Range,
+};
+
+type T = (char, (char, char));
+
+// the canonical decomposition of every composed letter, sorted by the letter
+static COMPOSITIONS: [T; 60] = [
+    ('Ã', ('A', '\u{303}')),
+    ('Ê', ('E', '\u{302}')),
+    ('Ñ', ('N', '\u{303}')),
+    ('Ô', ('O', '\u{302}')),
+    ('ã', ('a', '\u{303}')),
+    ('ê', ('e', '\u{302}')),
+    ('ñ', ('n', '\u{303}')),
+    ('ô', ('o', '\u{302}')),
+    ('Ā', ('A', '\u{304}')),
+    ('ā', ('a', '\u{304}')),
+    ('Ē', ('E', '\u{304}')),
+    ('ē', ('e', '\u{304}')),
+    ('Ġ', ('G', '\u{307}')),
+    ('ġ', ('g', '\u{307}')),
+    ('Ī', ('I', '\u{304}')),
+    ('ī', ('i', '\u{304}')),
+    ('Ō', ('O', '\u{304}')),
+    ('ō', ('o', '\u{304}')),
+    ('Ś', ('S', '\u{301}')),
+    ('ś', ('s', '\u{301}')),
+    ('Ū', ('U', '\u{304}')),
+    ('ū', ('u', '\u{304}')),
+    ('ऩ', ('न', '\u{93c}')),
+    ('ऱ', ('र', '\u{93c}')),
+    ('ऴ', ('ळ', '\u{93c}')),
+    ('Ḍ', ('D', '\u{323}')),
+    ('ḍ', ('d', '\u{323}')),
+    ('Ḥ', ('H', '\u{323}')),
+    ('ḥ', ('h', '\u{323}')),
+    ('Ḵ', ('K', '\u{331}')),
+    ('ḵ', ('k', '\u{331}')),
+    ('Ḷ', ('L', '\u{323}')),
+    ('ḷ', ('l', '\u{323}')),
+    ('Ḹ', ('Ḷ', '\u{304}')),
+    ('ḹ', ('ḷ', '\u{304}')),
+    ('Ḻ', ('L', '\u{331}')),
+    ('ḻ', ('l', '\u{331}')),
+    ('Ṁ', ('M', '\u{307}')),
+    ('ṁ', ('m', '\u{307}')),
+    ('Ṃ', ('M', '\u{323}')),
+    ('ṃ', ('m', '\u{323}')),
+    ('Ṅ', ('N', '\u{307}')),
+    ('ṅ', ('n', '\u{307}')),
+    ('Ṇ', ('N', '\u{323}')),
+    ('ṇ', ('n', '\u{323}')),
+    ('Ṉ', ('N', '\u{331}')),
+    ('ṉ', ('n', '\u{331}')),
+    ('Ṛ', ('R', '\u{323}')),
+    ('ṛ', ('r', '\u{323}')),
+    ('Ṝ', ('Ṛ', '\u{304}')),
+    ('ṝ', ('ṛ', '\u{304}')),
+    ('Ṟ', ('R', '\u{331}')),
+    ('ṟ', ('r', '\u{331}')),
+    ('Ṣ', ('S', '\u{323}')),
+    ('ṣ', ('s', '\u{323}')),
+    ('Ṭ', ('T', '\u{323}')),
+    ('ṭ', ('t', '\u{323}')),
+    ('Ẏ', ('Y', '\u{307}')),
+    ('ẏ', ('y', '\u{307}')),
+    ('ẖ', ('h', '\u{331}')),
+];
+
+// the nukta letters that stay decomposed in NFC as well, sorted by the letter
+static EXCLUSIONS: [T; 8] = [
+    ('\u{958}', ('क', '\u{93c}')),
+    ('\u{959}', ('ख', '\u{93c}')),
+    ('\u{95a}', ('ग', '\u{93c}')),
+    ('\u{95b}', ('ज', '\u{93c}')),
+    ('\u{95c}', ('ड', '\u{93c}')),
+    ('\u{95d}', ('ढ', '\u{93c}')),
+    ('\u{95e}', ('फ', '\u{93c}')),
+    ('\u{95f}', ('य', '\u{93c}')),
+];
+
+// canonical combining classes of the marks that can follow these letters, sorted by the mark
+static COMBINING_CLASSES: [(char, u8); 13] = [
+    ('\u{300}', 230),
+    ('\u{301}', 230),
+    ('\u{302}', 230),
+    ('\u{303}', 230),
+    ('\u{304}', 230),
+    ('\u{307}', 230),
+    ('\u{323}', 220),
+    ('\u{325}', 220),
+    ('\u{331}', 220),
+    ('\u{93c}', 7),
+    ('\u{94d}', 9),
+    ('\u{951}', 230),
+    ('\u{952}', 220),
+];
+
+fn combining_class(c: char) -> u8 {
+    binary_search(&COMBINING_CLASSES, c, |v| v).unwrap_or(0)
+}
+
+fn decomposition(c: char) -> Option<(char, char)> {
+    binary_search(&COMPOSITIONS, c, |v| v).or_else(|| nukta_decomposition(c))
+}
+
+fn composition(a: char, b: char) -> Option<char> {
+    COMPOSITIONS.iter().find(|v| v.1 == (a, b)).map(|v| v.0)
+}
+
+/// The letter and the nukta a nukta letter like क़ is written as in NFC
+pub(crate) fn nukta_decomposition(c: char) -> Option<(char, char)> {
+    binary_search(&EXCLUSIONS, c, |v| v)
+}
+
+/// The nukta letter that `c` followed by a nukta stands for, like क़ for क
+pub(crate) fn nukta_letter(c: char) -> Option<char> {
+    EXCLUSIONS
+        .iter()
+        .find(|v| v.1 == (c, '\u{93c}'))
+        .map(|v| v.0)
+}
+
+// the letters of a word that NFC could change
+fn is_decomposed(c: char) -> bool {
+    matches!(
+        c,
+        '\u{301}'..='\u{304}' | '\u{307}' | '\u{323}' | '\u{331}' | '\u{93c}'
+    ) || nukta_decomposition(c).is_some()
+}
+
+fn decompose(c: char, f: &mut dyn FnMut(char)) {
+    match decomposition(c) {
+        Some((a, b)) => {
+            decompose(a, f);
+            f(b);
+        }
+        None => f(c),
+    }
+}
+
+/// Writes `word` in NFC to `out`, along with the bytes of `out` and of `word` of every letter and
+/// the marks that follow it. Returns false and leaves both untouched when `word` is in NFC already.
+pub(crate) fn nfc(
+    word: &str,
+    out: &mut String,
+    segments: &mut Vec<(Range<usize>, Range<usize>)>,
+) -> bool {
+    if !word.chars().any(is_decomposed) {
+        return false;
+    }
+
+    out.clear();
+    segments.clear();
+
+    let mut buf = Vec::new();
+    let mut chars = word.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        buf.clear();
+        decompose(c, &mut |v| buf.push(v));
+
+        let mut end = start + c.len_utf8();
+        while let Some(&(i, m)) = chars.peek() {
+            if combining_class(m) == 0 {
+                break;
+            }
+
+            decompose(m, &mut |v| buf.push(v));
+            end = i + m.len_utf8();
+            chars.next();
+        }
+
+        // canonical ordering, then every mark that is not blocked by an earlier mark of the same
+        // class is composed with the letter
+        buf[1..].sort_by_key(|&v| combining_class(v));
+
+        let mut n = 1;
+        for i in 1..buf.len() {
+            let m = buf[i];
+            let blocked = n > 1 && combining_class(buf[n - 1]) >= combining_class(m);
+
+            match composition(buf[0], m) {
+                Some(v) if !blocked => buf[0] = v,
+                _ => {
+                    buf[n] = m;
+                    n += 1;
+                }
+            }
+        }
+
+        let len = out.len();
+        out.extend(&buf[..n]);
+        segments.push((len..out.len(), start..end));
+    }
+
+    true
+}
+
+/// Writes `s` to `out` with every letter decomposed. Returns the number of bytes and characters
+/// written.
+pub(crate) fn write_nfd(s: &str, out: &mut dyn Write) -> Result<(usize, usize), fmt::Error> {
+    let (mut len, mut chars) = (0, 0);
+    let mut res = Ok(());
+
+    for c in s.chars() {
+        decompose(c, &mut |v| {
+            if res.is_ok() {
+                res = out.write_char(v);
+                len += v.len_utf8();
+                chars += 1;
+            }
+        });
+        res?;
+    }
+
+    Ok((len, chars))
+}
@@ -0,0 +1,253 @@
+//! The Roman schemes that ship with the crate, with their letters sorted at compile time
+
+use crate::{
+    dn,
+    engine::{self, Char, Letter, Parse, Render},
+    tables::Category,
+    utils::{Context, binary_search},
+};
+use core::{
+    fmt::{self, Write},
+    mem,
+};
+
+/// A देवनागरी character along with every spelling of it, the first of which is written
+pub(crate) type Spellings = (char, &'static [&'static str]);
+
+// a spelling along with what it stands for
+type Reading = (&'static str, Category, char);
+
+/// The letters of a Roman scheme that ships with the crate, each group sorted by देवनागरी
+#[derive(Clone, Copy)]
+pub(crate) struct Letters {
+    pub(crate) vowels: &'static [Spellings],
+    pub(crate) vowel_signs: &'static [Spellings],
+    pub(crate) consonants: &'static [Spellings],
+    pub(crate) misc: &'static [Spellings],
+}
+
+impl Letters {
+    const fn groups(&self) -> [(Category, &'static [Spellings]); 4] {
+        [
+            (Category::Vowel, self.vowels),
+            (Category::VowelSign, self.vowel_signs),
+            (Category::Consonant, self.consonants),
+            (Category::Misc, self.misc),
+        ]
+    }
+
+    /// The number of spellings
+    pub(crate) const fn count(&self) -> usize {
+        let groups = self.groups();
+        let (mut n, mut g) = (0, 0);
+
+        while g < groups.len() {
+            let mut i = 0;
+            while i < groups[g].1.len() {
+                n += groups[g].1[i].1.len();
+                i += 1;
+            }
+            g += 1;
+        }
+
+        n
+    }
+
+    /// The length in characters of the longest spelling
+    pub(crate) const fn longest(&self) -> usize {
+        let groups = self.groups();
+        let (mut res, mut g) = (0, 0);
+
+        while g < groups.len() {
+            let mut i = 0;
+            while i < groups[g].1.len() {
+                let spellings = groups[g].1[i].1;
+                let mut j = 0;
+                while j < spellings.len() {
+                    // every byte but the continuation bytes starts a character
+                    let bytes = spellings[j].as_bytes();
+                    let (mut n, mut k) = (0, 0);
+                    while k < bytes.len() {
+                        n += (bytes[k] & 0xC0 != 0x80) as usize;
+                        k += 1;
+                    }
+                    if n > res {
+                        res = n;
+                    }
+                    j += 1;
+                }
+                i += 1;
+            }
+            g += 1;
+        }
+
+        res
+    }
+
+    /// Every spelling along with what it stands for, sorted by spelling. `N` is
+    /// [`count`](Self::count).
+    pub(crate) const fn readings<const N: usize>(&self) -> [Reading; N] {
+        // bytewise, which is the order of the characters as well
+        const fn less(a: &str, b: &str) -> bool {
+            let (a, b) = (a.as_bytes(), b.as_bytes());
+            let mut i = 0;
+            while i < a.len() && i < b.len() {
+                if a[i] != b[i] {
+                    return a[i] < b[i];
+                }
+                i += 1;
+            }
+            a.len() < b.len()
+        }
+
+        let groups = self.groups();
+        let mut res = [("", Category::Misc, '\0'); N];
+        let (mut n, mut g) = (0, 0);
+
+        while g < groups.len() {
+            let (category, arr) = groups[g];
+            let mut i = 0;
+            while i < arr.len() {
+                let (c, spellings) = arr[i];
+                let mut j = 0;
+                while j < spellings.len() {
+                    // insertion sort, which keeps the readings of a spelling in the order of the
+                    // groups
+                    let mut k = n;
+                    while k > 0 && less(spellings[j], res[k - 1].0) {
+                        res[k] = res[k - 1];
+                        k -= 1;
+                    }
+                    res[k] = (spellings[j], category, c);
+                    n += 1;
+                    j += 1;
+                }
+                i += 1;
+            }
+            g += 1;
+        }
+        assert!(n == N, "`N` is the number of spellings");
+
+        res
+    }
+}
+
+/// A Roman scheme that ships with the crate
+pub(crate) struct Roman {
+    pub(crate) letters: Letters,
+    /// [`Letters::readings`]
+    pub(crate) readings: &'static [Reading],
+    /// [`Letters::longest`]
+    pub(crate) longest: usize,
+    pub(crate) inherent: &'static str,
+    /// Characters without a spelling that are written as is
+    pub(crate) copies: &'static [char],
+    /// Read as nothing, and written between spellings that would otherwise be read as one
+    pub(crate) separator: Option<&'static str>,
+    /// Spellings of conjuncts, longest first, read as the letters they are made of
+    pub(crate) escapes: &'static [(&'static str, &'static str)],
+    /// Whether capitals are read as small letters
+    pub(crate) lowercase: bool,
+}
+
+impl Roman {
+    // every spelling of the देवनागरी character `c`, along with its group
+    fn find(&self, c: char) -> Option<(&'static [&'static str], Category)> {
+        self.letters
+            .groups()
+            .into_iter()
+            .find_map(|(k, arr)| binary_search(arr, c, |v| (v, k)))
+    }
+
+    /// Every देवनागरी letter along with each of its spellings, the written one first
+    pub(crate) fn table(&'static self) -> impl Iterator<Item = (Category, char, &'static str)> {
+        dn::table().flat_map(move |(k, c)| {
+            let spellings = self.find(c).map_or(&[][..], |v| v.0);
+            spellings.iter().map(move |&v| (k, c, v))
+        })
+    }
+
+    /// Reads a word into देवनागरी
+    pub(crate) fn read(&self, word: &str, ctx: &mut Context, out: &mut dyn Write) -> fmt::Result {
+        let mut chars = mem::take(&mut ctx.chars);
+        chars.clear();
+
+        engine::resolve(word, self.escapes, self.lowercase, &mut chars);
+        let res = engine::read_roman(word, &chars, self, ctx, out);
+
+        ctx.chars = chars;
+        res
+    }
+
+    /// Writes a देवनागरी word
+    pub(crate) fn write(&self, dn: &str, ctx: &mut Context, out: &mut dyn Write) -> fmt::Result {
+        engine::write_roman(dn, self, ctx, out)
+    }
+}
+
+impl Parse for Roman {
+    fn longest_match(&self, data: &[Char]) -> Option<(usize, Letter)> {
+        (1..=self.longest.min(data.len())).rev().find_map(|n| {
+            let key = || data[..n].iter().map(|c| c.c);
+            let start = self.readings.partition_point(|v| v.0.chars().lt(key()));
+
+            let mut l = Letter::default();
+            for &(_, k, c) in self.readings[start..]
+                .iter()
+                .take_while(|v| v.0.chars().eq(key()))
+            {
+                l.slot(k, c).get_or_insert(c);
+            }
+            (l != Letter::default()).then_some((n, l))
+        })
+    }
+
+    // only between letters that would otherwise be read as one spelling, so that the same
+    // character anywhere else is read like any other character the scheme does not have
+    fn separator_at(&self, prev: &[Char], data: &[Char]) -> Option<usize> {
+        let v = self.separator?;
+        let n = v.chars().count();
+        if data.len() < n || !v.chars().eq(data[..n].iter().map(|c| c.c)) {
+            return None;
+        }
+
+        let next = &data[n..];
+        (1..=self.longest.min(prev.len()))
+            .any(|k| {
+                (1..=(self.longest - k).min(next.len())).any(|m| {
+                    let key = || prev[prev.len() - k..].iter().chain(&next[..m]).map(|c| c.c);
+                    let start = self.readings.partition_point(|v| v.0.chars().lt(key()));
+                    self.readings
+                        .get(start)
+                        .is_some_and(|v| v.0.chars().eq(key()))
+                })
+            })
+            .then_some(n)
+    }
+}
+
+impl Render for Roman {
+    fn spelling(&self, c: char) -> Option<(&'static str, Category)> {
+        self.find(c).map(|(v, k)| (v[0], k))
+    }
+
+    fn inherent(&self) -> &'static str {
+        self.inherent
+    }
+
+    fn copies(&self, c: char) -> bool {
+        self.copies.contains(&c)
+    }
+
+    // `prev` followed by the start of `next` would be read as a longer spelling
+    fn separator(&self, prev: &str, next: &str) -> Option<&'static str> {
+        let v = self.separator.filter(|_| !prev.is_empty())?;
+        let start = self.readings.partition_point(|v| v.0 <= prev);
+
+        self.readings[start..]
+            .iter()
+            .take_while(|v| v.0.starts_with(prev))
+            .any(|v| next.starts_with(&v.0[prev.len()..]))
+            .then_some(v)
+    }
+}
@@ -0,0 +1,543 @@
+//! Routing between the supported transliteration schemes
+
+#[cfg(feature = "gu")]
+use crate::gu;
+#[cfg(feature = "hk")]
+use crate::hk;
+#[cfg(feature = "iso")]
+use crate::iso;
+#[cfg(feature = "itrans")]
+use crate::itrans;
+#[cfg(feature = "slp")]
+use crate::slp;
+#[cfg(feature = "velthuis")]
+use crate::velthuis;
+#[cfg(feature = "wx")]
+use crate::wx;
+use crate::{
+    Diagnostic, SchemeDefinition, TransliterationError, Unmappable, engine,
+    utils::{Context, split_line_and_convert},
+};
+#[cfg(feature = "iast")]
+use crate::{iast, uast};
+use alloc::{string::String, sync::Arc, vec::Vec};
+use core::{
+    fmt::{self, Write},
+    hash::{Hash, Hasher},
+    iter, mem,
+    ops::Range,
+};
+
+/// A transliteration scheme that can be used as a source or as a target of [`transliterate`].
+///
+/// Two [`Scheme::Custom`] are equal only when they share the same definition. Every variant
+/// other than देवनागरी needs the feature of its scheme.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum Scheme {
+    /// देवनागरी
+    Devanāgarī,
+    /// ગુજરાતી
+    #[cfg(feature = "gu")]
+    Gujarātī,
+    /// International Alphabet of Sanskrit Transliteration, read along with the `/…/` escapes of
+    /// UAST-IO
+    #[cfg(feature = "iast")]
+    Iast,
+    /// UAST-IO. As a target, this produces IAST which is a subset of UAST-IO.
+    #[cfg(feature = "uast")]
+    UastIo,
+    /// Sanskrit Library Phonetic Basic
+    #[cfg(feature = "slp")]
+    Slp1,
+    /// Harvard-Kyoto
+    #[cfg(feature = "hk")]
+    HarvardKyoto,
+    /// ITRANS. Every spelling ITRANS allows for a letter is read, and the first one listed by
+    /// ITRANS is written.
+    #[cfg(feature = "itrans")]
+    Itrans,
+    /// Velthuis, the encoding of the devnag TeX preprocessor
+    #[cfg(feature = "velthuis")]
+    Velthuis,
+    /// ISO 15919, which tells the short e and o from the long ones
+    #[cfg(feature = "iso")]
+    Iso15919,
+    /// WX, the notation of the Indian NLP tools
+    #[cfg(feature = "wx")]
+    Wx,
+    /// A scheme loaded at runtime, like a [`CustomScheme`](crate::CustomScheme), or defined by
+    /// another crate.
+    Custom(Arc<dyn SchemeDefinition>),
+}
+
+impl PartialEq for Scheme {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Scheme::Custom(a), Scheme::Custom(b)) => Arc::ptr_eq(a, b),
+            _ => mem::discriminant(self) == mem::discriminant(other),
+        }
+    }
+}
+
+impl Eq for Scheme {}
+
+impl Hash for Scheme {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+        if let Scheme::Custom(v) = self {
+            Arc::as_ptr(v).cast::<()>().hash(state);
+        }
+    }
+}
+
+impl fmt::Display for Scheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Scheme::Devanāgarī => "देवनागरी",
+            #[cfg(feature = "gu")]
+            Scheme::Gujarātī => "ગુજરાતી",
+            #[cfg(feature = "iast")]
+            Scheme::Iast => "IAST",
+            #[cfg(feature = "uast")]
+            Scheme::UastIo => "UAST-IO",
+            #[cfg(feature = "slp")]
+            Scheme::Slp1 => "SLP1",
+            #[cfg(feature = "hk")]
+            Scheme::HarvardKyoto => "Harvard-Kyoto",
+            #[cfg(feature = "itrans")]
+            Scheme::Itrans => "ITRANS",
+            #[cfg(feature = "velthuis")]
+            Scheme::Velthuis => "Velthuis",
+            #[cfg(feature = "iso")]
+            Scheme::Iso15919 => "ISO 15919",
+            #[cfg(feature = "wx")]
+            Scheme::Wx => "WX",
+            Scheme::Custom(v) => v.name(),
+        })
+    }
+}
+
+/// What a converter does with a character it does not recognise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Unknown {
+    /// The character is left out of the output.
+    #[default]
+    Drop,
+    /// The character is copied to the output unchanged.
+    PassThrough,
+}
+
+/// Unicode normalization form of the output.
+///
+/// Input is read the same in either form. Only the letters with diacritics of the Roman schemes,
+/// like `ā`, and the nukta letters of देवनागरी differ between the two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Normalization {
+    /// Composed letters, like `ā`.
+    #[default]
+    Nfc,
+    /// Decomposed letters, like `a` followed by U+0304 COMBINING MACRON.
+    Nfd,
+}
+
+/// Options for [`transliterate_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Options {
+    /// What to do with characters that are not part of the source scheme.
+    pub unknown: Unknown,
+    /// Unicode normalization form of the output.
+    pub normalization: Normalization,
+}
+
+type Convertor = fn(&str, &mut Context, &mut dyn Write) -> fmt::Result;
+
+// देवनागरी is the pivot: every source is first brought to देवनागरी and then rendered in the target.
+fn to_devanāgarī(from: &Scheme) -> &'static [Convertor] {
+    match from {
+        Scheme::Devanāgarī => &[],
+        #[cfg(feature = "gu")]
+        Scheme::Gujarātī => &[gu::gujarātī_convertor],
+        #[cfg(feature = "iast")]
+        Scheme::Iast => &[uast::convertor],
+        #[cfg(feature = "uast")]
+        Scheme::UastIo => &[uast::convertor],
+        #[cfg(feature = "slp")]
+        Scheme::Slp1 => &[slp::slp_convertor],
+        #[cfg(feature = "hk")]
+        Scheme::HarvardKyoto => &[hk::hk_convertor],
+        #[cfg(feature = "itrans")]
+        Scheme::Itrans => &[itrans::itrans_convertor],
+        #[cfg(feature = "velthuis")]
+        Scheme::Velthuis => &[velthuis::velthuis_convertor],
+        #[cfg(feature = "iso")]
+        Scheme::Iso15919 => &[iso::iso_convertor],
+        #[cfg(feature = "wx")]
+        Scheme::Wx => &[wx::wx_convertor],
+        Scheme::Custom(_) => &[engine::reader],
+    }
+}
+
+fn from_devanāgarī(to: &Scheme) -> &'static [Convertor] {
+    match to {
+        Scheme::Devanāgarī => &[],
+        #[cfg(feature = "gu")]
+        Scheme::Gujarātī => &[gu::convertor],
+        #[cfg(feature = "iast")]
+        Scheme::Iast => &[iast::convertor],
+        #[cfg(feature = "uast")]
+        Scheme::UastIo => &[iast::convertor],
+        #[cfg(feature = "slp")]
+        Scheme::Slp1 => &[slp::convertor],
+        #[cfg(feature = "hk")]
+        Scheme::HarvardKyoto => &[hk::convertor],
+        #[cfg(feature = "itrans")]
+        Scheme::Itrans => &[itrans::convertor],
+        #[cfg(feature = "velthuis")]
+        Scheme::Velthuis => &[velthuis::convertor],
+        #[cfg(feature = "iso")]
+        Scheme::Iso15919 => &[iso::convertor],
+        #[cfg(feature = "wx")]
+        Scheme::Wx => &[wx::convertor],
+        Scheme::Custom(_) => &[engine::writer],
+    }
+}
+
+pub(crate) fn convert(text: &str, ctx: &mut Context, out: &mut dyn Write) -> fmt::Result {
+    let (a, b) = (to_devanāgarī(&ctx.from), from_devanāgarī(&ctx.to));
+
+    // every word goes through all the converters before moving on to the next one. the
+    // intermediate results go back and forth between the two scratch buffers of `ctx`.
+    split_line_and_convert(
+        |word, ctx, out| {
+            // NFC and NFD input are read the same
+            let mut normalized = mem::take(&mut ctx.normalized);
+            let input = if ctx.normalize(word, &mut normalized) {
+                normalized.as_str()
+            } else {
+                word
+            };
+
+            let res = convert_word(word, input, a, b, ctx, out);
+            ctx.normalized = normalized;
+            res
+        },
+        text,
+        ctx,
+        out,
+    )
+}
+
+// `input` is `word` once normalized
+fn convert_word(
+    word: &str,
+    input: &str,
+    a: &[Convertor],
+    b: &[Convertor],
+    ctx: &mut Context,
+    out: &mut dyn Write,
+) -> fmt::Result {
+    let n = a.len() + b.len();
+
+    if n == 0 {
+        if !ctx.align && ctx.opts.normalization == Normalization::Nfc {
+            return out.write_str(input);
+        }
+
+        ctx.start_stage(input);
+        for (i, c) in input.char_indices() {
+            ctx.write_char(out, i..i + c.len_utf8(), c)?;
+        }
+        ctx.end_word(word);
+
+        return Ok(());
+    }
+
+    let [mut prev, mut next] = mem::take(&mut ctx.stages);
+    let mut res = Ok(());
+
+    for (i, f) in a.iter().chain(b).enumerate() {
+        ctx.first_stage = i == 0;
+        ctx.last_stage = i + 1 == n;
+
+        let input = if i == 0 { input } else { prev.as_str() };
+        ctx.start_stage(input);
+        res = if ctx.last_stage {
+            run_stage(*f, input, ctx, out)
+        } else {
+            next.clear();
+            run_stage(*f, input, ctx, &mut next)
+        };
+        ctx.end_stage();
+
+        if res.is_err() {
+            break;
+        }
+        mem::swap(&mut prev, &mut next);
+    }
+
+    ctx.stages = [prev, next];
+    if res.is_ok() {
+        ctx.end_word(word);
+    }
+    res
+}
+
+// runs `f` over `input`, except for what the stage before copied through, which is copied again
+fn run_stage(f: Convertor, input: &str, ctx: &mut Context, out: &mut dyn Write) -> fmt::Result {
+    if ctx.prev_opaque.is_empty() {
+        return f(input, ctx, out);
+    }
+
+    let opaque = mem::take(&mut ctx.prev_opaque);
+    let mut res = Ok(());
+    let mut i = 0;
+
+    for v in opaque
+        .iter()
+        .cloned()
+        .chain(iter::once(input.len()..input.len()))
+    {
+        if i < v.start {
+            ctx.piece = i;
+            res = f(&input[i..v.start], ctx, out);
+            ctx.piece = 0;
+        }
+        if res.is_ok() && !v.is_empty() {
+            res = ctx.copy_through(out, v.clone(), &input[v.clone()]);
+        }
+        if res.is_err() {
+            break;
+        }
+        i = v.end;
+    }
+
+    ctx.prev_opaque = opaque;
+    res
+}
+
+// converters only fail when `out` does
+fn convert_to_string(text: &str, ctx: &mut Context) -> String {
+    let mut res = String::with_capacity(text.len());
+    convert(text, ctx, &mut res).expect("writing to a String does not fail");
+
+    res
+}
+
+/// This function converts `text` from any [`Scheme`] to any other [`Scheme`].
+///
+/// Conversions are routed through देवनागरी.
+///
+/// ```
+/// use uast::{Scheme, transliterate};
+///
+/// let s = "om BUrBuvaH svaH tatsaviturvareRyaM Bargo devasya DImahi. Diyo yo naH pracodayAt..";
+/// assert_eq!(
+///     "ઓમ્ ભૂર્ભુવઃ સ્વઃ તત્સવિતુર્વરેણ્યં ભર્ગો દેવસ્ય ધીમહિ। ધિયો યો નઃ પ્રચોદયાત્॥",
+///     transliterate(&s, Scheme::Slp1, Scheme::Gujarātī)
+/// );
+/// ```
+pub fn transliterate(text: &str, from: Scheme, to: Scheme) -> String {
+    transliterate_with(text, from, to, &Options::default())
+}
+
+/// This function is [`transliterate`] with [`Options`] for this call.
+///
+/// ```
+/// use uast::{Options, Scheme, Unknown, transliterate_with};
+///
+/// let opts = Options {
+///     unknown: Unknown::PassThrough,
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     "\"rāmaḥ\", vanaṃ gacchati? (Rāma goes to the forest)",
+///     transliterate_with(
+///         "\"रामः\", वनं गच्छति? (Rāma goes to the forest)",
+///         Scheme::Devanāgarī,
+///         Scheme::Iast,
+///         &opts
+///     )
+/// );
+/// ```
+pub fn transliterate_with(text: &str, from: Scheme, to: Scheme, opts: &Options) -> String {
+    convert_to_string(text, &mut Context::new(*opts, from, to))
+}
+
+/// This function is [`transliterate_with`] that appends to any [`fmt::Write`] instead of
+/// allocating a new `String`. Nothing is allocated for each character or word.
+///
+/// ```
+/// use uast::{Options, Scheme, transliterate_into};
+///
+/// let mut s = String::new();
+/// for l in ["Bargo devasya", "DImahi"] {
+///     transliterate_into(l, Scheme::Slp1, Scheme::Devanāgarī, &Options::default(), &mut s).unwrap();
+///     s.push('\n');
+/// }
+/// assert_eq!("भर्गो देवस्य\nधीमहि\n", s);
+/// ```
+pub fn transliterate_into<W>(
+    text: &str,
+    from: Scheme,
+    to: Scheme,
+    opts: &Options,
+    out: &mut W,
+) -> fmt::Result
+where
+    W: Write + ?Sized,
+{
+    let mut out = out;
+    convert(text, &mut Context::new(*opts, from, to), &mut out)
+}
+
+/// This function is [`transliterate`] that fails on every character of `text` that is not part of
+/// `from` instead of leaving it out.
+///
+/// ```
+/// use uast::{Scheme, try_transliterate};
+///
+/// assert_eq!(
+///     "ભર્ગો દેવસ્ય",
+///     try_transliterate("bhargo devasya", Scheme::Iast, Scheme::Gujarātī).unwrap()
+/// );
+///
+/// let err = try_transliterate("bhargo? devasya!", Scheme::Iast, Scheme::Gujarātī).unwrap_err();
+/// assert_eq!(
+///     "could not transliterate IAST: \"?\" at 6, \"!\" at 15",
+///     err.to_string()
+/// );
+/// ```
+pub fn try_transliterate(
+    text: &str,
+    from: Scheme,
+    to: Scheme,
+) -> Result<String, TransliterationError> {
+    let mut ctx = Context::new(Options::default(), from.clone(), to);
+    ctx.collect = true;
+    let res = convert_to_string(text, &mut ctx);
+
+    let unmappable = ctx
+        .take_diagnostics(text)
+        .into_iter()
+        .filter(|v| v.kind.is_lossy())
+        .map(Unmappable::from)
+        .collect::<Vec<Unmappable>>();
+
+    if unmappable.is_empty() {
+        Ok(res)
+    } else {
+        Err(TransliterationError {
+            scheme: from,
+            unmappable,
+        })
+    }
+}
+
+/// The output of [`transliterate_with_diagnostics`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Converted {
+    /// The transliterated text.
+    pub text: String,
+    /// Everything that was dropped or approximated, in the order it appears in the input.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// This function is [`transliterate_with`] that also reports every spot of `text` that was dropped
+/// or approximated, so that only those have to be reviewed.
+///
+/// ```
+/// use uast::{DiagnosticKind, Options, Scheme, transliterate_with_diagnostics};
+///
+/// let res = transliterate_with_diagnostics(
+///     "ॐ तत्स॑वि॒तुः",
+///     Scheme::Devanāgarī,
+///     Scheme::Iast,
+///     &Options::default(),
+/// );
+/// assert_eq!("ॐ tatsavituḥ", res.text);
+/// assert_eq!(
+///     vec![
+///         "\"ॐ\" (U+0950) copied as is to IAST at offset 0",
+///         "\"॑\" (U+0951) cannot be written in IAST at offset 6",
+///         "\"॒\" (U+0952) cannot be written in IAST at offset 9",
+///     ],
+///     res.diagnostics
+///         .iter()
+///         .map(|v| v.to_string())
+///         .collect::<Vec<_>>()
+/// );
+/// assert_eq!(DiagnosticKind::Unrepresentable(Scheme::Iast), res.diagnostics[1].kind);
+/// ```
+pub fn transliterate_with_diagnostics(
+    text: &str,
+    from: Scheme,
+    to: Scheme,
+    opts: &Options,
+) -> Converted {
+    let mut ctx = Context::new(*opts, from, to);
+    ctx.collect = true;
+    let res = convert_to_string(text, &mut ctx);
+
+    Converted {
+        text: res,
+        diagnostics: ctx.take_diagnostics(text),
+    }
+}
+
+/// Characters `source` of the input that were written as characters `output` of the output.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Alignment {
+    /// Character range in the input.
+    pub source: Range<usize>,
+    /// Character range in the output.
+    pub output: Range<usize>,
+}
+
+/// The output of [`transliterate_with_alignment`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Aligned {
+    /// The transliterated text.
+    pub text: String,
+    /// Which characters of the input every part of `text` was written for, in the order of
+    /// `text`. Dropped characters are not part of any range.
+    pub alignment: Vec<Alignment>,
+}
+
+/// This function is [`transliterate_with`] that also tells which span of `text` produced which
+/// span of the output, so that a match found in one script can be highlighted in the other.
+///
+/// The letters of an aspirate or a diphthong map to the one character written for them, and the
+/// inherent vowel to the consonant it follows. The halanta inserted after a consonant maps back
+/// to that consonant.
+///
+/// ```
+/// use uast::{Alignment, Options, Scheme, transliterate_with_alignment};
+///
+/// let res = transliterate_with_alignment("bhargo", Scheme::Iast, Scheme::Devanāgarī, &Options::default());
+/// assert_eq!("भर्गो", res.text);
+/// assert_eq!(
+///     vec![
+///         Alignment { source: 0..3, output: 0..1 }, // bha → भ
+///         Alignment { source: 3..4, output: 1..3 }, // r → र्
+///         Alignment { source: 4..5, output: 3..4 }, // g → ग
+///         Alignment { source: 5..6, output: 4..5 }, // o → ो
+///     ],
+///     res.alignment
+/// );
+/// ```
+pub fn transliterate_with_alignment(
+    text: &str,
+    from: Scheme,
+    to: Scheme,
+    opts: &Options,
+) -> Aligned {
+    let mut ctx = Context::new(*opts, from, to);
+    ctx.align = true;
+    let text = convert_to_string(text, &mut ctx);
+
+    Aligned {
+        text,
+        alignment: ctx.alignment,
+    }
+}
@@ -0,0 +1,460 @@
+//! Splitting a document into runs of one script, to convert only some of them
+
+#[cfg(feature = "gu")]
+use crate::gu;
+#[cfg(feature = "iast")]
+use crate::iast;
+#[cfg(feature = "uast")]
+use crate::uast;
+use crate::{
+    DiagnosticKind, Options, Scheme, ScriptKind, TokenKind, dn,
+    scheme::convert,
+    utils::{Context, Sink, is_devanāgarī, is_gujarātī, lowercase},
+};
+use alloc::{string::String, vec, vec::Vec};
+use core::ops::Range;
+
+/// The script of a [`Segment`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Script {
+    /// देवनागरी, along with the Vedic Extensions and देवनागरी Extended blocks.
+    Devanāgarī,
+    /// ગુજરાતી
+    Gujarātī,
+    /// Roman words with a letter of IAST that English does not use, like `ā` or `ṣ`, or with a
+    /// UAST-IO escape.
+    Iast,
+    /// Any other Roman words, like English prose.
+    Latin,
+    /// Whitespace, digits and punctuation between the runs.
+    Other,
+}
+
+/// A run of text in one [`Script`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Segment {
+    /// The script of the run.
+    pub script: Script,
+    /// Byte range of the run in the text.
+    pub span: Range<usize>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Class {
+    Script(Script),
+    // daṇḍas are used by ગુજરાતી as well
+    Shared,
+    Neutral,
+}
+
+fn class(c: char) -> Class {
+    let devanāgarī = dn::token_kind(c) != TokenKind::Other || is_devanāgarī(c);
+    #[cfg(feature = "gu")]
+    let gujarātī = gu::get_devanāgarī(c).is_some() || is_gujarātī(c);
+    #[cfg(not(feature = "gu"))]
+    let gujarātī = is_gujarātī(c);
+
+    match (devanāgarī, gujarātī) {
+        (true, true) => Class::Shared,
+        (true, false) => Class::Script(Script::Devanāgarī),
+        (false, true) => Class::Script(Script::Gujarātī),
+        // combining marks of decomposed IAST, and what UAST-IO and SLP1 use besides letters
+        _ if c.is_alphabetic() || matches!(c, '\u{300}'..='\u{36F}' | '/' | '~') => {
+            Class::Script(Script::Latin)
+        }
+        _ => Class::Neutral,
+    }
+}
+
+// IAST letters that English does not use, and UAST-IO escapes
+fn is_iast(word: &str, letters: &[char]) -> bool {
+    if word
+        .chars()
+        .any(|c| matches!(c, '\u{300}'..='\u{36F}') || letters.binary_search(&lowercase(c)).is_ok())
+    {
+        return true;
+    }
+
+    #[cfg(feature = "uast")]
+    {
+        let parts = word.split('/').collect::<Vec<_>>();
+        if parts
+            .iter()
+            .skip(1)
+            .step_by(2)
+            .take((parts.len() - 1) / 2)
+            .any(|k| uast::is_escape(k))
+        {
+            return true;
+        }
+    }
+
+    false
+}
+
+fn push(res: &mut Vec<Segment>, script: Script, span: Range<usize>) {
+    match res.last_mut() {
+        Some(v) if v.script == script && v.span.end == span.start => v.span.end = span.end,
+        _ => res.push(Segment { script, span }),
+    }
+}
+
+// splits a word into runs. punctuation inside a run of one script belongs to it, and daṇḍas go
+// with the ગુજરાતી or देवनागरी next to them.
+fn word(text: &str, offset: usize, letters: &[char], res: &mut Vec<Segment>) {
+    let mut chars = text
+        .char_indices()
+        .map(|(i, c)| (offset + i..offset + i + c.len_utf8(), class(c)))
+        .collect::<Vec<_>>();
+
+    let indic = |v: &(Range<usize>, Class)| {
+        matches!(
+            v.1,
+            Class::Script(Script::Devanāgarī) | Class::Script(Script::Gujarātī)
+        )
+    };
+    for i in 0..chars.len() {
+        if chars[i].1 == Class::Shared {
+            chars[i].1 = chars[..i]
+                .iter()
+                .rev()
+                .find(|v| indic(v))
+                .or_else(|| chars[i..].iter().find(|v| indic(v)))
+                .map_or(Class::Script(Script::Devanāgarī), |v| v.1);
+        }
+    }
+
+    for i in 0..chars.len() {
+        if chars[i].1 != Class::Neutral {
+            continue;
+        }
+
+        let prev = chars[..i].iter().rev().find(|v| v.1 != Class::Neutral);
+        let next = chars[i..].iter().find(|v| v.1 != Class::Neutral);
+        match (prev, next) {
+            (Some(a), Some(b)) if a.1 == b.1 => chars[i].1 = a.1,
+            _ => (),
+        }
+    }
+
+    let mut i = 0;
+    while i < chars.len() {
+        let class = chars[i].1;
+        let n = chars[i..].iter().take_while(|v| v.1 == class).count();
+        let span = chars[i].0.start..chars[i + n - 1].0.end;
+        i += n;
+
+        let script = match class {
+            Class::Script(Script::Latin)
+                if is_iast(&text[span.start - offset..span.end - offset], letters) =>
+            {
+                Script::Iast
+            }
+            Class::Script(v) => v,
+            _ => Script::Other,
+        };
+        push(res, script, span);
+    }
+}
+
+// the runs of every word, with the whitespace between the words
+fn runs(text: &str) -> Vec<Segment> {
+    #[cfg(feature = "iast")]
+    let mut letters = iast::table()
+        .filter_map(|v| v.2)
+        .flat_map(str::chars)
+        .filter(|c| !c.is_ascii())
+        .collect::<Vec<_>>();
+    #[cfg(not(feature = "iast"))]
+    let mut letters = Vec::new();
+    letters.sort_unstable();
+    letters.dedup();
+
+    let mut res = Vec::new();
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        let offset = text.len() - rest.len();
+        let n = rest
+            .find(|c: char| !c.is_whitespace())
+            .unwrap_or(rest.len());
+        if n > 0 {
+            push(&mut res, Script::Other, offset..offset + n);
+        }
+        rest = &rest[n..];
+
+        let offset = text.len() - rest.len();
+        let n = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        if n > 0 {
+            word(&rest[..n], offset, &letters, &mut res);
+        }
+        rest = &rest[n..];
+    }
+
+    res
+}
+
+// a plain Roman word that could be Sanskrit without its diacritics: two letters at least, all of
+// them small letters that IAST has, no vowels next to each other but `ai` and `au`, an `h` only
+// after a vowel or a consonant that it aspirates, a `c` and a `y` only before a vowel, and ending
+// in a vowel or in one of the consonants a word ends in before a pause
+fn could_be_sanskrit(word: &str) -> bool {
+    let vowel = |c: char| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u');
+    let chars = word.chars().collect::<Vec<_>>();
+
+    chars.len() >= 2
+        && chars
+            .iter()
+            .all(|&c| matches!(c, 'a'..='e' | 'g'..='p' | 'r'..='v' | 'y'))
+        && chars.windows(2).all(|v| match (v[0], v[1]) {
+            ('a', 'i' | 'u') => true,
+            (a, 'h') => vowel(a) || matches!(a, 'b' | 'c' | 'd' | 'g' | 'j' | 'k' | 'p' | 't'),
+            ('c', b) => vowel(b) || matches!(b, 'c' | 'y'),
+            ('y', b) => vowel(b),
+            (a, b) => !(vowel(a) && vowel(b)),
+        })
+        && match chars[..] {
+            [.., c] if vowel(c) => true,
+            [.., a, 'k' | 't' | 'n' | 'm'] => vowel(a),
+            _ => false,
+        }
+}
+
+// a Roman word with something plain English does not have: a capital after the first letter, a
+// letter with a diacritic or punctuation inside it. digits and apostrophes are plain, and so are
+// words in capitals only, like acronyms.
+fn is_marked(word: &str) -> bool {
+    let plain = |(i, c): (usize, char)| {
+        c.is_ascii_lowercase()
+            || c.is_ascii_digit()
+            || matches!(c, '\'' | '’')
+            || (i == 0 && c.is_ascii_uppercase())
+    };
+
+    word.char_indices().any(|v| !plain(v))
+        && !word
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || !c.is_alphabetic())
+}
+
+// takes the Roman words next to the `taken` runs, with only whitespace between them, as long as
+// every word between a taken run and the next punctuation, other script or taken run `could_be`
+// in the scheme. a citation is taken whole, while one English word keeps the prose around it out.
+fn extend(
+    text: &str,
+    runs: &[Segment],
+    taken: &mut [bool],
+    could_be: &mut dyn FnMut(&str) -> bool,
+) {
+    let roman = |v: &Segment| matches!(v.script, Script::Iast | Script::Latin);
+    let space = |v: &Segment| v.script == Script::Other && text[v.span.clone()].trim().is_empty();
+
+    let mut i = 0;
+    while i < runs.len() {
+        if !roman(&runs[i]) {
+            i += 1;
+            continue;
+        }
+
+        // Roman words with only whitespace between them
+        let mut words = vec![i];
+        while i + 2 < runs.len() && space(&runs[i + 1]) && roman(&runs[i + 2]) {
+            i += 2;
+            words.push(i);
+        }
+        i += 1;
+
+        let mut start = 0;
+        while start < words.len() {
+            if taken[words[start]] {
+                start += 1;
+                continue;
+            }
+
+            let end = words[start..]
+                .iter()
+                .position(|&k| taken[k])
+                .map_or(words.len(), |n| start + n);
+            if (start > 0 || end < words.len())
+                && words[start..end]
+                    .iter()
+                    .all(|&k| could_be(&text[runs[k].span.clone()]))
+            {
+                for &k in &words[start..end] {
+                    taken[k] = true;
+                }
+            }
+            start = end;
+        }
+    }
+}
+
+// whitespace between two runs of the same script belongs to them
+fn merge(text: &str, runs: Vec<Segment>) -> Vec<Segment> {
+    let mut res = Vec::<Segment>::with_capacity(runs.len());
+    let mut segments = runs.into_iter().peekable();
+    while let Some(v) = segments.next() {
+        let whitespace = v.script == Script::Other && text[v.span.clone()].trim().is_empty();
+
+        match (res.last_mut(), segments.peek()) {
+            (Some(p), Some(n))
+                if whitespace && p.script == n.script && p.script != Script::Other =>
+            {
+                p.span.end = n.span.end;
+                segments.next();
+            }
+            _ => push(&mut res, v.script, v.span),
+        }
+    }
+
+    res
+}
+
+/// This function splits `text` into runs of one [`Script`]. The runs cover all of `text`, in
+/// order.
+///
+/// A Roman word is only taken for IAST when it has a letter that English does not use or a
+/// UAST-IO escape, so `deva` on its own is [`Script::Latin`]. The plain words next to it are taken
+/// along with it when every one of them up to the next punctuation, other script or IAST word
+/// could be Sanskrit without its diacritics: small letters that IAST has, no vowels next to each
+/// other but `ai` and `au`, and ending in a vowel, `k`, `t`, `n` or `m`. So `bhargo devasya
+/// dhīmahi` is one run, while in `or bhargo devasya dhīmahi` the English `or` leaves the words
+/// before `dhīmahi` as they are. An English word like `here` on its own next to a citation can
+/// still be taken for Sanskrit. Whitespace between two runs of the same script belongs to them.
+///
+/// ```
+/// use uast::{Script, segment};
+///
+/// let s = "The verse भर्गो देवस्य (bhargo devasya dhīmahi) means…";
+/// assert_eq!(
+///     vec![
+///         (Script::Latin, "The verse"),
+///         (Script::Other, " "),
+///         (Script::Devanāgarī, "भर्गो देवस्य"),
+///         (Script::Other, " ("),
+///         (Script::Iast, "bhargo devasya dhīmahi"),
+///         (Script::Other, ") "),
+///         (Script::Latin, "means"),
+///         (Script::Other, "…"),
+///     ],
+///     segment(s)
+///         .into_iter()
+///         .map(|v| (v.script, &s[v.span]))
+///         .collect::<Vec<_>>()
+/// );
+/// ```
+pub fn segment(text: &str) -> Vec<Segment> {
+    let mut res = runs(text);
+
+    let mut taken = res
+        .iter()
+        .map(|v| v.script == Script::Iast)
+        .collect::<Vec<_>>();
+    extend(text, &res, &mut taken, &mut could_be_sanskrit);
+    for (v, taken) in res.iter_mut().zip(taken) {
+        if taken {
+            v.script = Script::Iast;
+        }
+    }
+
+    merge(text, res)
+}
+
+/// This function converts the runs of `text` that are in the script of `from` to `to`, and leaves
+/// everything else byte for byte as it is. See [`segment`] for how the runs are found.
+///
+/// The other Roman schemes look like English, so a word is only read by one of them when the
+/// scheme has every character of it and it has something plain English does not: a capital after
+/// the first letter, like `BUrBuvaH`, a letter with a diacritic, or punctuation inside it, like
+/// `k.r.s.Na`. The plain words next to it are taken along with it as for IAST. A word like `Bargo`
+/// is left as it is, as its capital may just start a sentence.
+///
+/// ```
+/// use uast::{Options, Scheme, transliterate_segments};
+///
+/// let s = "As the Gītā says, कर्मण्येवाधिकारस्ते मा फलेषु कदाचन।\n";
+/// assert_eq!(
+///     "As the Gītā says, karmaṇyevādhikāraste mā phaleṣu kadācana.\n",
+///     transliterate_segments(s, Scheme::Devanāgarī, Scheme::Iast, &Options::default())
+/// );
+/// assert_eq!(
+///     "As the गीता says, कर्मण्येवाधिकारस्ते मा फलेषु कदाचन।\n",
+///     transliterate_segments(s, Scheme::Iast, Scheme::Devanāgarī, &Options::default())
+/// );
+///
+/// let s = "The mantra BUrBuvaH svaH begins the verse.";
+/// assert_eq!(
+///     "The mantra भूर्भुवः स्वः begins the verse.",
+///     transliterate_segments(s, Scheme::Slp1, Scheme::Devanāgarī, &Options::default())
+/// );
+/// ```
+pub fn transliterate_segments(text: &str, from: Scheme, to: Scheme, opts: &Options) -> String {
+    let runs = runs(text);
+    let mut taken = runs
+        .iter()
+        .map(|v| match &from {
+            Scheme::Devanāgarī => v.script == Script::Devanāgarī,
+            #[cfg(feature = "gu")]
+            Scheme::Gujarātī => v.script == Script::Gujarātī,
+            _ => false,
+        })
+        .collect::<Vec<_>>();
+
+    match &from {
+        Scheme::Devanāgarī => (),
+        #[cfg(feature = "gu")]
+        Scheme::Gujarātī => (),
+        #[cfg(feature = "iast")]
+        Scheme::Iast => iast_runs(text, &runs, &mut taken),
+        #[cfg(feature = "uast")]
+        Scheme::UastIo => iast_runs(text, &runs, &mut taken),
+        Scheme::Custom(v) if v.kind() == ScriptKind::Brahmic => (),
+        _ => {
+            // whether `from` reads every character of a word
+            let mut probe = Context::new(Options::default(), from.clone(), Scheme::Devanāgarī);
+            probe.collect = true;
+            let mut reads = |word: &str| {
+                probe.diagnostics.clear();
+                convert(word, &mut probe, &mut Sink).expect("nothing is written");
+                !probe
+                    .diagnostics
+                    .iter()
+                    .any(|v| matches!(v.kind, DiagnosticKind::Unknown(_)))
+            };
+
+            for (v, taken) in runs.iter().zip(taken.iter_mut()) {
+                let word = &text[v.span.clone()];
+                *taken = matches!(v.script, Script::Iast | Script::Latin)
+                    && is_marked(word)
+                    && reads(word);
+            }
+            extend(text, &runs, &mut taken, &mut |word| {
+                could_be_sanskrit(word) && reads(word)
+            });
+        }
+    }
+
+    let mut ctx = Context::new(*opts, from, to);
+    let mut res = String::with_capacity(text.len());
+
+    for (v, taken) in runs.into_iter().zip(taken) {
+        let s = &text[v.span];
+
+        if taken {
+            convert(s, &mut ctx, &mut res).expect("writing to a String does not fail");
+        } else {
+            res.push_str(s);
+        }
+    }
+
+    res
+}
+
+// the IAST runs along with the plain words next to them, as `segment` finds them
+#[cfg(feature = "iast")]
+fn iast_runs(text: &str, runs: &[Segment], taken: &mut [bool]) {
+    for (v, taken) in runs.iter().zip(taken.iter_mut()) {
+        *taken = v.script == Script::Iast;
+    }
+    extend(text, runs, taken, &mut could_be_sanskrit);
+}
@@ -1,79 +1,120 @@
-//! SLP1 to IAST
+//! SLP1 to देवनागरी and देवनागरी to SLP1
 
-use crate::utils::{binary_search, split_line_and_convert};
+use crate::{
+    Options, Scheme, TransliterationError,
+    roman::{Letters, Roman},
+    transliterate, transliterate_into, try_transliterate,
+    utils::Context,
+};
+use alloc::string::String;
+use core::fmt::{self, Write};
 
-static CHAR_DICT: [(char, &str); 63] = [
-    ('\'', "'"),
-    ('.', "."),
-    ('0', "0"),
-    ('1', "1"),
-    ('2', "2"),
-    ('3', "3"),
-    ('4', "4"),
-    ('5', "5"),
-    ('6', "6"),
-    ('7', "7"),
-    ('8', "8"),
-    ('9', "9"),
-    ('A', "ā"),
-    ('B', "bh"),
-    ('C', "ch"),
-    ('D', "dh"),
-    ('E', "ai"),
-    ('F', "ṝ"),
-    ('G', "gh"),
-    ('H', "ḥ"),
-    ('I', "ī"),
-    ('J', "jh"),
-    ('K', "kh"),
-    ('L', "ḻ"),
-    ('M', "ṃ"),
-    ('N', "ṅ"),
-    ('O', "au"),
-    ('P', "ph"),
-    ('Q', "ḍh"),
-    ('R', "ṇ"),
-    ('S', "ś"),
-    ('T', "th"),
-    ('U', "ū"),
-    ('W', "ṭh"),
-    ('X', "ḹ"),
-    ('Y', "ñ"),
-    ('a', "a"),
-    ('b', "b"),
-    ('c', "c"),
-    ('d', "d"),
-    ('e', "e"),
-    ('f', "ṛ"),
-    ('g', "g"),
-    ('h', "h"),
-    ('i', "i"),
-    ('j', "j"),
-    ('k', "k"),
-    ('l', "l"),
-    ('m', "m"),
-    ('n', "n"),
-    ('o', "o"),
-    ('p', "p"),
-    ('q', "ḍ"),
-    ('r', "r"),
-    ('s', "s"),
-    ('t', "t"),
-    ('u', "u"),
-    ('v', "v"),
-    ('w', "ṭ"),
-    ('x', "ḷ"),
-    ('y', "y"),
-    ('z', "ṣ"),
-    ('~', "ã"),
-];
+// one character for every letter, so that aspirates and diphthongs never need to be kept apart
+const LETTERS: Letters = Letters {
+    vowels: &[
+        ('अ', &["a"]),
+        ('आ', &["A"]),
+        ('इ', &["i"]),
+        ('ई', &["I"]),
+        ('उ', &["u"]),
+        ('ऊ', &["U"]),
+        ('ऋ', &["f"]),
+        ('ऌ', &["x"]),
+        ('ए', &["e"]),
+        ('ऐ', &["E"]),
+        ('ओ', &["o"]),
+        ('औ', &["O"]),
+        ('ॠ', &["F"]),
+        ('ॡ', &["X"]),
+    ],
+    vowel_signs: &[
+        ('ा', &["A"]),
+        ('ि', &["i"]),
+        ('ी', &["I"]),
+        ('ु', &["u"]),
+        ('ू', &["U"]),
+        ('ृ', &["f"]),
+        ('ॄ', &["F"]),
+        ('े', &["e"]),
+        ('ै', &["E"]),
+        ('ो', &["o"]),
+        ('ौ', &["O"]),
+        ('ॢ', &["x"]),
+        ('ॣ', &["X"]),
+    ],
+    consonants: &[
+        ('क', &["k"]),
+        ('ख', &["K"]),
+        ('ग', &["g"]),
+        ('घ', &["G"]),
+        ('ङ', &["N"]),
+        ('च', &["c"]),
+        ('छ', &["C"]),
+        ('ज', &["j"]),
+        ('झ', &["J"]),
+        ('ञ', &["Y"]),
+        ('ट', &["w"]),
+        ('ठ', &["W"]),
+        ('ड', &["q"]),
+        ('ढ', &["Q"]),
+        ('ण', &["R"]),
+        ('त', &["t"]),
+        ('थ', &["T"]),
+        ('द', &["d"]),
+        ('ध', &["D"]),
+        ('न', &["n"]),
+        ('प', &["p"]),
+        ('फ', &["P"]),
+        ('ब', &["b"]),
+        ('भ', &["B"]),
+        ('म', &["m"]),
+        ('य', &["y"]),
+        ('र', &["r"]),
+        ('ल', &["l"]),
+        ('ळ', &["L"]),
+        ('व', &["v"]),
+        ('श', &["S"]),
+        ('ष', &["z"]),
+        ('स', &["s"]),
+        ('ह', &["h"]),
+    ],
+    misc: &[
+        ('ँ', &["~"]),
+        ('ं', &["M"]),
+        ('ः', &["H"]),
+        ('ऽ', &["'"]),
+        ('।', &["."]),
+        ('॥', &[".."]),
+        ('०', &["0"]),
+        ('१', &["1"]),
+        ('२', &["2"]),
+        ('३', &["3"]),
+        ('४', &["4"]),
+        ('५', &["5"]),
+        ('६', &["6"]),
+        ('७', &["7"]),
+        ('८', &["8"]),
+        ('९', &["9"]),
+    ],
+};
 
-fn get_char(c: char) -> Option<&'static str> {
-    binary_search(&CHAR_DICT, c, |i| i)
+pub(crate) static SCHEME: Roman = Roman {
+    letters: LETTERS,
+    readings: &LETTERS.readings::<{ LETTERS.count() }>(),
+    longest: LETTERS.longest(),
+    inherent: "a",
+    copies: &[],
+    separator: None,
+    escapes: &[],
+    lowercase: false,
+};
+
+pub(crate) fn slp_convertor(slp: &str, ctx: &mut Context, out: &mut dyn Write) -> fmt::Result {
+    SCHEME.read(slp, ctx, out)
 }
 
-fn convertor(dn: &str) -> String {
-    dn.chars().filter_map(get_char).collect()
+pub(crate) fn convertor(dn: &str, ctx: &mut Context, out: &mut dyn Write) -> fmt::Result {
+    SCHEME.write(dn, ctx, out)
 }
 
 /// This function converts SLP to IAST
@@ -88,5 +129,33 @@ fn convertor(dn: &str) -> String {
 /// );
 /// ```
 pub fn slp_to_iast(dn: &str) -> String {
-    split_line_and_convert(convertor, dn)
+    transliterate(dn, Scheme::Slp1, Scheme::Iast)
+}
+
+/// This function converts SLP to IAST and fails on any character that is not SLP.
+///
+/// ```
+/// use uast::try_slp_to_iast;
+///
+/// assert_eq!("bhargo", try_slp_to_iast("Bargo").unwrap());
+/// assert_eq!(5, try_slp_to_iast("Bargo, Bargo").unwrap_err().unmappable[0].char_offset);
+/// ```
+pub fn try_slp_to_iast(dn: &str) -> Result<String, TransliterationError> {
+    try_transliterate(dn, Scheme::Slp1, Scheme::Iast)
+}
+
+/// This function is [`slp_to_iast`] that appends to `out` instead of allocating.
+///
+/// ```
+/// use uast::slp_to_iast_into;
+///
+/// let mut s = String::from("1. ");
+/// slp_to_iast_into("namaH", &mut s).unwrap();
+/// assert_eq!("1. namaḥ", s);
+/// ```
+pub fn slp_to_iast_into<W>(dn: &str, out: &mut W) -> fmt::Result
+where
+    W: Write + ?Sized,
+{
+    transliterate_into(dn, Scheme::Slp1, Scheme::Iast, &Options::default(), out)
 }
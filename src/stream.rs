@@ -0,0 +1,105 @@
+//! Streaming transliteration over `io::Read` and `io::Write`
+
+use crate::{Options, Scheme, scheme::convert, utils::Context};
+use std::{
+    io::{self, ErrorKind, Read, Write},
+    str,
+};
+
+const CHUNK_SIZE: usize = 8 * 1024;
+
+fn invalid_data(e: str::Utf8Error) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, e)
+}
+
+/// This function reads `reader` chunk by chunk and writes its transliteration to `writer` without
+/// loading the whole input.
+///
+/// Every token of every scheme lies within a word, so a chunk is only converted up to its last
+/// whitespace and the rest is carried over to the next chunk. Aspirates, diphthongs, `..` and
+/// `/…/` escapes split across two reads thus come out the same as with [`transliterate_with`](crate::transliterate_with).
+/// A single word is always held in memory as a whole.
+///
+/// ```
+/// use uast::{Options, Scheme, transliterate_stream};
+///
+/// let mut out = Vec::new();
+/// transliterate_stream(
+///     "/om/ bhūrbhuvaḥ svaḥ..\n".as_bytes(),
+///     &mut out,
+///     Scheme::UastIo,
+///     Scheme::Devanāgarī,
+///     &Options::default(),
+/// )
+/// .unwrap();
+/// assert_eq!("ॐ भूर्भुवः स्वः॥\n", String::from_utf8(out).unwrap());
+/// ```
+pub fn transliterate_stream<R, W>(
+    mut reader: R,
+    mut writer: W,
+    from: Scheme,
+    to: Scheme,
+    opts: &Options,
+) -> io::Result<()>
+where
+    R: Read,
+    W: Write,
+{
+    let mut ctx = Context::new(*opts, from, to);
+    let mut buf = vec![0; CHUNK_SIZE];
+    // bytes at the start of `buf` of a character split across two reads
+    let mut carried = 0;
+    let mut pending = String::with_capacity(CHUNK_SIZE);
+    let mut res = String::with_capacity(CHUNK_SIZE);
+
+    loop {
+        let n = match reader.read(&mut buf[carried..]) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+
+        // only the bytes of this read are checked
+        let read = &buf[..carried + n];
+        let s = match str::from_utf8(read) {
+            Ok(s) => s,
+            Err(e) if e.error_len().is_none() => str::from_utf8(&read[..e.valid_up_to()])
+                .expect("prefix up to `valid_up_to` is valid UTF-8"),
+            Err(e) => return Err(invalid_data(e)),
+        };
+        let start = pending.len();
+        pending.push_str(s);
+
+        let valid = s.len();
+        buf.copy_within(valid..carried + n, 0);
+        carried = carried + n - valid;
+
+        // what was read before has no whitespace left in it
+        let end = pending[start..]
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map_or(0, |(i, c)| start + i + c.len_utf8());
+
+        if end > 0 {
+            write(&pending[..end], &mut ctx, &mut res, &mut writer)?;
+            pending.drain(..end);
+        }
+    }
+
+    if carried > 0 {
+        str::from_utf8(&buf[..carried]).map_err(invalid_data)?;
+    }
+    write(&pending, &mut ctx, &mut res, &mut writer)
+}
+
+fn write<W>(text: &str, ctx: &mut Context, res: &mut String, writer: &mut W) -> io::Result<()>
+where
+    W: Write,
+{
+    res.clear();
+    convert(text, ctx, res).map_err(io::Error::other)?;
+    writer.write_all(res.as_bytes())?;
+    writer.flush()
+}
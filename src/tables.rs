@@ -0,0 +1,175 @@
+//! Read-only access to the letters of every [`Scheme`] and the देवनागरी they stand for
+//!
+//! ```
+//! use uast::{Scheme, tables::{self, Category}};
+//!
+//! let consonants = tables::entries(Scheme::Slp1)
+//!     .into_iter()
+//!     .filter(|v| v.category == Category::Consonant)
+//!     .map(|v| v.text)
+//!     .collect::<Vec<_>>();
+//! assert_eq!(34, consonants.len());
+//! assert_eq!(["k", "K", "g", "G", "N"], consonants[..5]);
+//! ```
+
+#[cfg(feature = "gu")]
+use crate::gu;
+#[cfg(feature = "hk")]
+use crate::hk;
+#[cfg(feature = "iast")]
+use crate::iast;
+#[cfg(feature = "iso")]
+use crate::iso;
+#[cfg(feature = "itrans")]
+use crate::itrans;
+#[cfg(any(
+    feature = "slp",
+    feature = "hk",
+    feature = "itrans",
+    feature = "velthuis",
+    feature = "iso",
+    feature = "wx"
+))]
+use crate::roman::Roman;
+#[cfg(feature = "slp")]
+use crate::slp;
+#[cfg(feature = "uast")]
+use crate::uast;
+#[cfg(feature = "velthuis")]
+use crate::velthuis;
+#[cfg(feature = "wx")]
+use crate::wx;
+use crate::{Scheme, dn, normalize};
+#[cfg(feature = "uast")]
+use alloc::format;
+use alloc::{string::String, vec::Vec};
+
+/// Version of the tables. It is bumped whenever an entry is added, removed or changed, so that
+/// anything generated from them can tell when to be regenerated.
+pub const VERSION: u32 = 7;
+
+/// What a letter is in a syllable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    /// An independent vowel.
+    Vowel,
+    /// A dependent vowel that follows a consonant.
+    VowelSign,
+    /// A consonant.
+    Consonant,
+    /// Avagraha, daṇḍas, digits and the like.
+    Misc,
+    /// Anusvāra, visarga, candrabindu, halanta, ॐ, ॰ and the Vedic accents.
+    Special,
+    /// A UAST-IO `/…/` escape, or an escape of a custom scheme.
+    Escape,
+}
+
+/// A letter of a scheme.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Entry {
+    /// What the letter is.
+    pub category: Category,
+    /// The letter as written in the scheme. For an escape, the whole `/…/` sequence.
+    pub text: String,
+    /// The देवनागरी character the letter stands for.
+    pub devanāgarī: char,
+}
+
+fn entry(category: Category, text: String, devanāgarī: char) -> Entry {
+    Entry {
+        category,
+        text,
+        devanāgarī,
+    }
+}
+
+#[cfg(any(
+    feature = "slp",
+    feature = "hk",
+    feature = "itrans",
+    feature = "velthuis",
+    feature = "iso",
+    feature = "wx"
+))]
+fn roman(scheme: &'static Roman) -> Vec<Entry> {
+    scheme
+        .table()
+        .map(|(k, c, v)| entry(k, v.into(), c))
+        .collect()
+}
+
+/// This function returns every letter of `scheme`, grouped by [`Category`].
+///
+/// A letter that stands for more than one देवनागरी character, like `ā` for both आ and ा, has an
+/// entry for each of them. A character with more than one spelling, like आ as both `A` and `aa`
+/// in ITRANS, has an entry for each spelling, the one that is written first. देवनागरी characters
+/// that `scheme` cannot write are left out.
+pub fn entries(scheme: Scheme) -> Vec<Entry> {
+    #[cfg(feature = "iast")]
+    let iast = || iast::table().filter_map(|(k, c, v)| Some(entry(k, v?.into(), c)));
+
+    match scheme {
+        // a nukta letter is written as the letter and the nukta, as in NFC
+        Scheme::Devanāgarī => dn::table()
+            .map(|(k, c)| match normalize::nukta_decomposition(c) {
+                Some((a, nukta)) => entry(k, [a, nukta].iter().collect(), c),
+                None => entry(k, c.into(), c),
+            })
+            .collect(),
+        #[cfg(feature = "gu")]
+        Scheme::Gujarātī => dn::table()
+            .filter_map(|(k, c)| Some(entry(k, gu::get_char(c)?.into(), c)))
+            .collect(),
+        #[cfg(feature = "iast")]
+        Scheme::Iast => iast().collect(),
+        #[cfg(feature = "uast")]
+        Scheme::UastIo => {
+            let mut res = iast().collect::<Vec<Entry>>();
+
+            for &(k, v) in uast::escapes() {
+                let text = format!("/{k}/");
+
+                // an escape either stands for a देवनागरी character or for an IAST letter
+                if dn::table().any(|(_, c)| c == v) {
+                    res.push(entry(Category::Escape, text, v));
+                    continue;
+                }
+
+                res.extend(
+                    iast::table()
+                        .filter(|(_, _, s)| s.is_some_and(|s| s.chars().eq([v])))
+                        .map(|(_, c, _)| entry(Category::Escape, text.clone(), c)),
+                );
+            }
+
+            res
+        }
+        #[cfg(feature = "slp")]
+        Scheme::Slp1 => roman(&slp::SCHEME),
+        #[cfg(feature = "hk")]
+        Scheme::HarvardKyoto => roman(&hk::SCHEME),
+        #[cfg(feature = "itrans")]
+        Scheme::Itrans => roman(&itrans::SCHEME),
+        #[cfg(feature = "iso")]
+        Scheme::Iso15919 => roman(&iso::SCHEME),
+        #[cfg(feature = "velthuis")]
+        Scheme::Velthuis => roman(&velthuis::SCHEME),
+        #[cfg(feature = "wx")]
+        Scheme::Wx => roman(&wx::SCHEME),
+        Scheme::Custom(v) => {
+            let mut res = v.entries().to_vec();
+
+            for (k, s) in v.escapes() {
+                res.extend(
+                    v.entries()
+                        .iter()
+                        .filter(|v| &v.text == s)
+                        .map(|v| entry(Category::Escape, k.clone(), v.devanāgarī)),
+                );
+            }
+
+            res
+        }
+    }
+}
@@ -1,5 +1,15 @@
 //! Tests
 
+// not every test is compiled with every feature
+#[allow(unused_imports)]
+use std::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+#[cfg(feature = "uast")]
 #[test]
 fn test_uast_to_devanāgarī() {
     use crate::uast_to_devanāgarī;
@@ -28,6 +38,7 @@ fn test_uast_to_devanāgarī() {
     }
 }
 
+#[cfg(feature = "iast")]
 #[test]
 fn test_devanāgarī_to_iast() {
     use crate::devanāgarī_to_iast;
@@ -52,8 +63,27 @@ fn test_devanāgarī_to_iast() {
     for (k, v) in arr {
         assert_eq!(devanāgarī_to_iast(k), v);
     }
+
+    // IAST reads the UAST-IO escapes whether or not `uast` is enabled
+    assert_eq!(
+        "ॐ नमः",
+        crate::transliterate("/om/ namaḥ", crate::Scheme::Iast, crate::Scheme::Devanāgarī)
+    );
 }
 
+// the first converters dropped a vowel after a syllable, and a vowel sign after a Vedic accent,
+// without a word. pass-through is there so that no text is lost, so they are read as they are.
+#[cfg(feature = "uast")]
+#[test]
+fn test_hiatus() {
+    use crate::{devanāgarī_to_iast, uast_to_devanāgarī};
+
+    assert_eq!("कअ", uast_to_devanāgarī("kaa"));
+    assert_eq!("kaa", devanāgarī_to_iast("कअ"));
+    assert_eq!("savituḥ", devanāgarī_to_iast("स॑वि॒तुः"));
+}
+
+#[cfg(feature = "gu")]
 #[test]
 fn test_devanāgarī_to_gujarātī() {
     use crate::devanāgarī_to_gujarātī;
@@ -79,6 +109,7 @@ fn test_devanāgarī_to_gujarātī() {
     }
 }
 
+#[cfg(feature = "slp")]
 #[test]
 fn test_slp_to_iast() {
     use crate::slp_to_iast;
@@ -103,3 +134,1844 @@ fn test_slp_to_iast() {
         assert_eq!(slp_to_iast(k), v);
     }
 }
+
+#[cfg(feature = "slp")]
+#[test]
+fn test_slp() {
+    use crate::{Scheme, transliterate};
+
+    // every letter is a single character, so a hiatus or a consonant before ह is read as it is
+    let arr = vec![
+        ("kh kai", "क्ह् कइ"),
+        ("kai prauga kha", "कइ प्रउग क्ह"),
+        ("kEtavam prOQa Kaga", "कैतवम् प्रौढ खग"),
+        ("agnimILe hotAraM ratnaDAtamam..", "अग्निमीळे होतारं रत्नधातमम्॥"),
+        ("kxpta pitFn sa devA~", "कॢप्त पितॄन् स देवाँ"),
+    ];
+
+    for (slp, dn) in arr {
+        assert_eq!(dn, transliterate(slp, Scheme::Slp1, Scheme::Devanāgarī));
+        assert_eq!(slp, transliterate(dn, Scheme::Devanāgarī, Scheme::Slp1));
+    }
+}
+
+#[cfg(feature = "hk")]
+#[test]
+fn test_hk() {
+    use crate::{Scheme, devanāgarī_to_hk, hk_to_devanāgarī, transliterate};
+
+    let arr = vec![
+        (
+            "maGgalaM bhagavAnviSNurmaGgalaM garuDadhvajaH | maGgalaM puNDarIkAkSo maGgalAyatanaM hariH ||",
+            "मङ्गलं भगवान्विष्णुर्मङ्गलं गरुडध्वजः । मङ्गलं पुण्डरीकाक्षो मङ्गलायतनं हरिः ॥",
+        ),
+        (
+            "agnimILe purohitaM yajJasya devamRtvijam | hotAraM ratnadhAtamam ||",
+            "अग्निमीळे पुरोहितं यज्ञस्य देवमृत्विजम् । होतारं रत्नधातमम् ॥",
+        ),
+        (
+            "zivaH ziSyaH kRSNaH klRptaH lRkAraH lRRkAraH pitRRn",
+            "शिवः शिष्यः कृष्णः कॢप्तः ऌकारः ॡकारः पितॄन्",
+        ),
+        (
+            "aizvaryam auSadham kaiTabhaH gauDaH sa devA~ eha 'gne 1947",
+            "ऐश्वर्यम् औषधम् कैटभः गौडः स देवाँ एह ऽग्ने १९४७",
+        ),
+        ("ThakkuraH DhakkA chAyA jhaSaH", "ठक्कुरः ढक्का छाया झषः"),
+    ];
+
+    for (hk, dn) in arr {
+        assert_eq!(dn, hk_to_devanāgarī(hk));
+        assert_eq!(hk, devanāgarī_to_hk(dn));
+    }
+
+    // HK is case sensitive, and what it cannot write is left out
+    assert_eq!("ष स", hk_to_devanāgarī("Sa sa"));
+    assert_eq!(
+        "oMkAraH",
+        transliterate("ओंकारः", Scheme::Devanāgarī, Scheme::HarvardKyoto)
+    );
+    #[cfg(feature = "iast")]
+    assert_eq!(
+        "rAmaH",
+        transliterate("rāmaḥ", Scheme::Iast, Scheme::HarvardKyoto)
+    );
+}
+
+#[cfg(all(feature = "gu", feature = "uast", feature = "slp"))]
+#[test]
+fn test_transliterate() {
+    use crate::{Scheme, transliterate};
+
+    let arr = vec![
+        (
+            "maNgalaM BagavAnvizRurmaNgalaM garuqaDvajaH.",
+            Scheme::Slp1,
+            Scheme::Devanāgarī,
+            "मङ्गलं भगवान्विष्णुर्मङ्गलं गरुडध्वजः।",
+        ),
+        (
+            "મઙ્ગલં ભગવાન્વિષ્ણુર્મઙ્ગલં ગરુડધ્વજઃ।",
+            Scheme::Gujarātī,
+            Scheme::Iast,
+            "maṅgalaṃ bhagavānviṣṇurmaṅgalaṃ garuḍadhvajaḥ.",
+        ),
+        (
+            "maṅgalaṃ bhagavānviṣṇurmaṅgalaṃ garuḍadhvajaḥ.",
+            Scheme::Iast,
+            Scheme::Slp1,
+            "maNgalaM BagavAnvizRurmaNgalaM garuqaDvajaH.",
+        ),
+        (
+            "ma/nu/gala/m/ hari/h/..",
+            Scheme::UastIo,
+            Scheme::Gujarātī,
+            "મઙ્ગલં હરિઃ॥",
+        ),
+        (
+            "ma/nu/gala/m/ hari/h/..",
+            Scheme::UastIo,
+            Scheme::Iast,
+            "maṅgalaṃ hariḥ..",
+        ),
+        ("मङ्गलं", Scheme::Devanāgarī, Scheme::Devanāgarī, "मङ्गलं"),
+        ("x", Scheme::Gujarātī, Scheme::Slp1, ""),
+    ];
+
+    for (k, from, to, v) in arr {
+        assert_eq!(transliterate(k, from, to), v);
+    }
+}
+
+#[cfg(all(feature = "gu", feature = "uast", feature = "slp"))]
+#[test]
+fn test_whitespace() {
+    use crate::{
+        devanāgarī_to_gujarātī, devanāgarī_to_iast, slp_to_iast, uast_to_devanāgarī
+    };
+
+    let arr = vec![
+        ("  agniḥ\tpūrvebhiḥ  \n", "  अग्निः\tपूर्वेभिः  \n"),
+        ("\n\nsa devāã eha vakṣati..\r\n", "\n\nस देवाँ एह वक्षति॥\r\n"),
+        ("\t", "\t"),
+        ("", ""),
+    ];
+
+    for (k, v) in arr {
+        assert_eq!(uast_to_devanāgarī(k), v);
+        assert_eq!(devanāgarī_to_iast(v), k);
+    }
+
+    assert_eq!(devanāgarī_to_gujarātī("  स\tदेवाँ\n"), "  સ\tદેવાઁ\n");
+    assert_eq!(slp_to_iast("\tsa  devA~\n"), "\tsa  devāã\n");
+}
+
+#[cfg(all(feature = "gu", feature = "uast", feature = "slp"))]
+#[test]
+fn test_pass_through() {
+    use crate::{Options, Scheme, Unknown, transliterate_with};
+
+    let opts = Options {
+        unknown: Unknown::PassThrough,
+        ..Default::default()
+    };
+
+    let arr = vec![
+        ("x", Scheme::UastIo, Scheme::Devanāgarī, "x"),
+        ("X", Scheme::UastIo, Scheme::Devanāgarī, "X"),
+        (
+            "/xy/ k/xy/a",
+            Scheme::UastIo,
+            Scheme::Devanāgarī,
+            "/xy/ क्/xy/अ",
+        ),
+        (
+            "(agniḥ), ka?",
+            Scheme::Iast,
+            Scheme::Devanāgarī,
+            "(अग्निः), क?",
+        ),
+        (
+            "\"अग्निः\", क?",
+            Scheme::Devanāgarī,
+            Scheme::Iast,
+            "\"agniḥ\", ka?",
+        ),
+        (
+            "राम, \"x\"",
+            Scheme::Devanāgarī,
+            Scheme::Gujarātī,
+            "રામ, \"x\"",
+        ),
+        (
+            "રામ, \"x\"",
+            Scheme::Gujarātī,
+            Scheme::Devanāgarī,
+            "राम, \"x\"",
+        ),
+        ("rAma, \"?\"", Scheme::Slp1, Scheme::Iast, "rāma, \"?\""),
+        ("rāma, \"?\"", Scheme::Iast, Scheme::Slp1, "rAma, \"?\""),
+        // what one converter copies through is not read again by the next one
+        (
+            "राम (the chief) said ṣ",
+            Scheme::Devanāgarī,
+            Scheme::Slp1,
+            "rAma (the chief) said ṣ",
+        ),
+        (
+            "રામ (the chief) said ṣ",
+            Scheme::Gujarātī,
+            Scheme::Slp1,
+            "rAma (the chief) said ṣ",
+        ),
+        ("rāmaḥ (राम)", Scheme::Iast, Scheme::Gujarātī, "રામઃ (राम)"),
+        ("rAma (राम)", Scheme::Slp1, Scheme::Iast, "rāma (राम)"),
+        // a vowel sign that cannot be written takes the place of the inherent vowel all the same
+        ("कॆवल", Scheme::Devanāgarī, Scheme::Iast, "kॆvala"),
+        ("कॊ", Scheme::Devanāgarī, Scheme::Slp1, "kॊ"),
+    ];
+
+    for (k, from, to, v) in arr {
+        assert_eq!(transliterate_with(k, from, to, &opts), v);
+    }
+    assert_eq!(
+        "kvala",
+        transliterate_with("कॆवल", Scheme::Devanāgarī, Scheme::Iast, &Options::default())
+    );
+}
+
+#[cfg(all(feature = "gu", feature = "uast", feature = "slp"))]
+#[test]
+fn test_strict() {
+    use crate::{Scheme, try_transliterate};
+
+    let arr = vec![
+        ("bhargo devasya", Scheme::Iast, Scheme::Devanāgarī, vec![]),
+        (
+            "ānanda x",
+            Scheme::Iast,
+            Scheme::Devanāgarī,
+            vec![("x", 8, 7)],
+        ),
+        (
+            "k/xy/a /a/",
+            Scheme::UastIo,
+            Scheme::Slp1,
+            vec![("/xy/", 1, 1)],
+        ),
+        (
+            "धियो, यो",
+            Scheme::Devanāgarī,
+            Scheme::Iast,
+            vec![(",", 12, 4)],
+        ),
+        ("ક्", Scheme::Devanāgarī, Scheme::Gujarātī, vec![("ક", 0, 0)]),
+        ("Diyo yo 1!", Scheme::Slp1, Scheme::Iast, vec![("!", 9, 9)]),
+        ("dhiyo\tyo ü", Scheme::Iast, Scheme::Slp1, vec![("ü", 9, 9)]),
+    ];
+
+    for (k, from, to, v) in arr {
+        let res = try_transliterate(k, from.clone(), to);
+
+        if v.is_empty() {
+            assert!(res.is_ok());
+            continue;
+        }
+
+        let err = res.unwrap_err();
+        assert_eq!(err.scheme, from);
+        assert_eq!(
+            err.unmappable
+                .iter()
+                .map(|i| (i.text.as_str(), i.byte_offset, i.char_offset))
+                .collect::<Vec<_>>(),
+            v
+        );
+    }
+}
+
+#[cfg(all(feature = "gu", feature = "uast", feature = "slp"))]
+#[test]
+fn test_diagnostics() {
+    use crate::{DiagnosticKind, Options, Scheme, Unknown, transliterate_with_diagnostics};
+
+    let pass = Options {
+        unknown: Unknown::PassThrough,
+        ..Default::default()
+    };
+
+    let arr = vec![
+        (
+            "bhargo x /xy/",
+            Scheme::Iast,
+            Scheme::Devanāgarī,
+            Options::default(),
+            "भर्गो  ",
+            vec![
+                (DiagnosticKind::Unknown(Scheme::Iast), "x", 7),
+                (DiagnosticKind::UnknownEscape, "/xy/", 9),
+            ],
+        ),
+        (
+            "bhargo, x",
+            Scheme::Iast,
+            Scheme::Gujarātī,
+            pass,
+            "ભર્ગો, x",
+            vec![
+                (DiagnosticKind::Unknown(Scheme::Iast), ",", 6),
+                (DiagnosticKind::Unknown(Scheme::Iast), "x", 8),
+            ],
+        ),
+        (
+            "bhargo de/-/vasya",
+            Scheme::UastIo,
+            Scheme::Iast,
+            Options::default(),
+            "bhargo devasya",
+            vec![(DiagnosticKind::Unrepresentable(Scheme::Iast), "॒", 7)],
+        ),
+        (
+            "ॐ भर्गो",
+            Scheme::Devanāgarī,
+            Scheme::Slp1,
+            Options::default(),
+            " Bargo",
+            vec![(DiagnosticKind::Unrepresentable(Scheme::Slp1), "ॐ", 0)],
+        ),
+        (
+            "ॐ भर्गो",
+            Scheme::Devanāgarī,
+            Scheme::Gujarātī,
+            Options::default(),
+            "ૐ ભર્ગો",
+            vec![],
+        ),
+    ];
+
+    for (k, from, to, opts, text, v) in arr {
+        let res = transliterate_with_diagnostics(k, from, to, &opts);
+
+        assert_eq!(res.text, text);
+        assert_eq!(
+            res.diagnostics
+                .iter()
+                .map(|i| (i.kind.clone(), i.text.as_str(), i.char_offset))
+                .collect::<Vec<_>>(),
+            v
+        );
+    }
+}
+
+#[cfg(all(feature = "std", feature = "gu", feature = "uast", feature = "slp"))]
+#[test]
+fn test_stream() {
+    use crate::{Options, Scheme, transliterate_stream, transliterate_with};
+    use std::io::{self, Read};
+
+    // hands out the input one byte at a time to split every token and character
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.0.is_empty() || buf.is_empty() {
+                return Ok(0);
+            }
+
+            buf[0] = self.0[0];
+            self.0 = &self.0[1..];
+            Ok(1)
+        }
+    }
+
+    let arr = vec![
+        (
+            "ma/nu/gala/m/ bhagav/a/nvi/sl//nl/urma/nu/gala/m/ garu/d/adhvaja/h/.\n\tma/nu/gala/m/ hari/h/..\n",
+            Scheme::UastIo,
+            Scheme::Devanāgarī,
+        ),
+        (
+            "अग्निमीळे पुरोहितं   यज्ञस्य देवमृत्विजम्।\r\nहोतारं रत्नधातमम्॥",
+            Scheme::Devanāgarī,
+            Scheme::Gujarātī,
+        ),
+        ("saiṣā bhagavataudāryāt..", Scheme::Iast, Scheme::Slp1),
+    ];
+
+    for (k, from, to) in arr {
+        let mut out = Vec::new();
+
+        transliterate_stream(
+            Trickle(k.as_bytes()),
+            &mut out,
+            from.clone(),
+            to.clone(),
+            &Options::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            transliterate_with(k, from, to, &Options::default())
+        );
+    }
+
+    let err = transliterate_stream(
+        &[b'k', 0xff, b' '][..],
+        io::sink(),
+        Scheme::Iast,
+        Scheme::Devanāgarī,
+        &Options::default(),
+    )
+    .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    // the input ends in the middle of a character
+    let err = transliterate_stream(
+        Trickle(&[b'k', b' ', 0xe0, 0xa4]),
+        io::sink(),
+        Scheme::Iast,
+        Scheme::Devanāgarī,
+        &Options::default(),
+    )
+    .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+// counts the allocations of each thread, so that the tests running alongside are not counted
+struct Counting;
+
+std::thread_local! {
+    static ALLOCATIONS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+unsafe impl std::alloc::GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|v| v.set(v.get() + 1));
+        unsafe { std::alloc::System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+        unsafe { std::alloc::System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+#[cfg(all(feature = "gu", feature = "iast"))]
+#[test]
+fn test_into_allocations() {
+    use crate::{devanāgarī_to_gujarātī_into, devanāgarī_to_iast_into};
+
+    // unknown characters are not recorded when nobody reads them
+    let mut out = String::with_capacity(64);
+    let n = ALLOCATIONS.with(|v| v.get());
+    devanāgarī_to_gujarātī_into("राम, \"x\" hello world", &mut out).unwrap();
+    devanāgarī_to_iast_into("ॐ तत्स॑वि॒तुः", &mut out).unwrap();
+    assert_eq!(n, ALLOCATIONS.with(|v| v.get()));
+}
+
+#[cfg(all(feature = "gu", feature = "uast", feature = "slp"))]
+#[test]
+fn test_into() {
+    use crate::{
+        Options, Scheme, Unknown, devanāgarī_to_gujarātī_into, devanāgarī_to_iast_into,
+        slp_to_iast_into, transliterate_into, transliterate_with, uast_to_devanāgarī_into,
+    };
+    use std::fmt;
+
+    // a writer that can only be reached through `dyn fmt::Write`
+    struct Lines(Vec<String>);
+
+    impl fmt::Write for Lines {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0.last_mut().unwrap().push_str(s);
+            Ok(())
+        }
+    }
+
+    let arr = vec![
+        (
+            "ma/nu/gala/m/ bhagav/a/n ?",
+            Scheme::UastIo,
+            Scheme::Devanāgarī,
+        ),
+        (
+            "धियो यो नः  प्रचोदयात्॥ x",
+            Scheme::Devanāgarī,
+            Scheme::Gujarātī,
+        ),
+        ("saiṣā bhagavataudāryāt..", Scheme::Iast, Scheme::Slp1),
+        ("Bargo devasya", Scheme::Slp1, Scheme::Slp1),
+        ("ભર્ગો દેવસ્ય", Scheme::Gujarātī, Scheme::Iast),
+    ];
+
+    for opts in [
+        Options::default(),
+        Options {
+            unknown: Unknown::PassThrough,
+            ..Default::default()
+        },
+    ] {
+        let mut out = Lines(Vec::new());
+
+        for (k, from, to) in &arr {
+            out.0.push(String::new());
+            transliterate_into(
+                k,
+                from.clone(),
+                to.clone(),
+                &opts,
+                &mut out as &mut dyn fmt::Write,
+            )
+            .unwrap();
+        }
+
+        assert_eq!(
+            arr.iter()
+                .map(|(k, from, to)| transliterate_with(k, from.clone(), to.clone(), &opts))
+                .collect::<Vec<String>>(),
+            out.0
+        );
+    }
+
+    let mut s = String::from(">");
+    uast_to_devanāgarī_into("bhargo", &mut s).unwrap();
+    devanāgarī_to_iast_into(" भर्गो", &mut s).unwrap();
+    devanāgarī_to_gujarātī_into(" भर्गो", &mut s).unwrap();
+    slp_to_iast_into(" Bargo", &mut s).unwrap();
+    assert_eq!(">भर्गो bhargo ભર્ગો bhargo", s);
+}
+
+#[cfg(all(feature = "gu", feature = "uast", feature = "slp"))]
+#[test]
+fn test_chars() {
+    use crate::{
+        Options, Scheme, Transliterate, TransliterateIter, Unknown, transliterate,
+        transliterate_with,
+    };
+
+    let arr = vec![
+        (
+            "  ma/nu/gala/m/ bhagav/a/n?\n",
+            Scheme::UastIo,
+            Scheme::Devanāgarī,
+        ),
+        ("धियो यो नः  प्रचोदयात्॥", Scheme::Devanāgarī, Scheme::Iast),
+        ("saiṣā bhagavataudāryāt..", Scheme::Iast, Scheme::Slp1),
+        ("ભર્ગો દેવસ્ય\t", Scheme::Gujarātī, Scheme::Slp1),
+        ("", Scheme::Slp1, Scheme::Iast),
+        (" \n ", Scheme::Slp1, Scheme::Iast),
+    ];
+
+    for (k, from, to) in arr {
+        let res = transliterate(k, from.clone(), to.clone());
+        assert_eq!(
+            res,
+            k.chars()
+                .transliterate_chars(from.clone(), to.clone())
+                .collect::<String>()
+        );
+        assert_eq!(res, k.transliterate_chars(from, to).collect::<String>());
+    }
+
+    let opts = Options {
+        unknown: Unknown::PassThrough,
+        ..Options::default()
+    };
+    let s = "bhargo devax? dhīmahi";
+    let res = transliterate_with(s, Scheme::Iast, Scheme::Devanāgarī, &opts);
+    assert_eq!("भर्गो देवx? धीमहि", res);
+    assert_eq!(
+        res,
+        s.transliterate_chars_with(Scheme::Iast, Scheme::Devanāgarī, &opts)
+            .collect::<String>()
+    );
+    assert_eq!(
+        res,
+        s.chars()
+            .transliterate_chars_with(Scheme::Iast, Scheme::Devanāgarī, &opts)
+            .collect::<String>()
+    );
+
+    // an iterator that cannot be cloned is taken by value
+    let mut s = String::from("Bargo");
+    assert_eq!(
+        "भर्गो",
+        s.drain(..)
+            .transliterate_chars(Scheme::Slp1, Scheme::Devanāgarī)
+            .collect::<String>()
+    );
+
+    let s = "bhargo ".repeat(1000);
+    assert_eq!(
+        "भर्गो भ",
+        s.transliterate_chars(Scheme::Iast, Scheme::Devanāgarī)
+            .take(7)
+            .collect::<String>()
+    );
+    assert_eq!(
+        "भर्गो भ",
+        core::iter::repeat("bhargo ".chars())
+            .flatten()
+            .transliterate_chars(Scheme::Iast, Scheme::Devanāgarī)
+            .take(7)
+            .collect::<String>()
+    );
+}
+
+#[cfg(all(feature = "gu", feature = "uast", feature = "slp"))]
+#[test]
+fn test_alignment() {
+    use crate::{Options, Scheme, Unknown, transliterate_with, transliterate_with_alignment};
+
+    let arr = vec![
+        (
+            "Bargo  devasya",
+            Scheme::Slp1,
+            Scheme::Devanāgarī,
+            vec![
+                (0..2, 0..1),
+                (2..3, 1..3),
+                (3..4, 3..4),
+                (4..5, 4..5),
+                (5..7, 5..7),
+                (7..8, 7..8),
+                (8..9, 8..9),
+                (9..11, 9..10),
+                (11..12, 10..12),
+                (12..14, 12..13),
+            ],
+        ),
+        (
+            "भर्गो",
+            Scheme::Devanāgarī,
+            Scheme::Slp1,
+            vec![(0..1, 0..2), (1..3, 2..3), (3..4, 3..4), (4..5, 4..5)],
+        ),
+        (
+            "ક્ષ",
+            Scheme::Gujarātī,
+            Scheme::Iast,
+            vec![(0..2, 0..1), (2..3, 1..3)],
+        ),
+        (
+            "maitrī? /om/",
+            Scheme::UastIo,
+            Scheme::Devanāgarī,
+            vec![
+                (0..1, 0..1),
+                (1..3, 1..2),
+                (3..4, 2..4),
+                (4..5, 4..5),
+                (5..6, 5..6),
+                (7..8, 6..7),
+                (8..12, 7..8),
+            ],
+        ),
+    ];
+
+    for (k, from, to, v) in arr {
+        let res = transliterate_with_alignment(k, from, to, &Options::default());
+        assert_eq!(
+            v,
+            res.alignment
+                .into_iter()
+                .map(|v| (v.source, v.output))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    // every character of the output is accounted for exactly once
+    let opts = Options {
+        unknown: Unknown::PassThrough,
+        ..Default::default()
+    };
+    for (k, from, to) in [
+        (
+            "dhiyo yo naḥ pracodayāt.. x",
+            Scheme::Iast,
+            Scheme::Gujarātī,
+        ),
+        ("ॐ तत्स॑वि॒तुः", Scheme::Devanāgarī, Scheme::Slp1),
+        ("Diyo yo naH", Scheme::Slp1, Scheme::Slp1),
+        ("kṛ(राम)ṣṇa", Scheme::Iast, Scheme::Gujarātī),
+    ] {
+        let res = transliterate_with_alignment(k, from.clone(), to.clone(), &opts);
+        assert_eq!(transliterate_with(k, from, to, &opts), res.text);
+
+        let mut end = 0;
+        for v in &res.alignment {
+            assert_eq!(end, v.output.start);
+            end = v.output.end;
+        }
+        assert_eq!(res.text.chars().count(), end);
+    }
+}
+
+#[cfg(feature = "uast")]
+#[test]
+fn test_tokens() {
+    use crate::{Token, TokenKind, tokenize_devanāgarī, tokenize_uast, uast_to_devanāgarī};
+
+    let arr = vec![
+        "/om/ bhūrbhuvaḥ svaḥ tatsaviturvareṇyaṃ",
+        "sa'ha-sra/'//-/ ka/m/su'da/ru/..",
+        "ãśaḥ kṣetre'pi 108 /./",
+        "aiśvarya autsukya",
+    ];
+
+    // both give the same tokens for the same text
+    for k in arr {
+        let dn = uast_to_devanāgarī(k);
+        assert_eq!(
+            tokenize_devanāgarī(&dn)
+                .into_iter()
+                .map(|v| (v.kind, v.letter))
+                .collect::<Vec<_>>(),
+            tokenize_uast(k)
+                .into_iter()
+                .map(|v| (v.kind, v.letter))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    let arr = vec![
+        (
+            tokenize_devanāgarī as fn(&str) -> Vec<Token>,
+            "ॐ अग्नि॑ऽः १।॥ ँ ॰ x",
+            vec![
+                (TokenKind::Om, 0..3),
+                (TokenKind::Vowel, 4..7),
+                (TokenKind::Consonant, 7..10),
+                (TokenKind::Halanta, 10..13),
+                (TokenKind::Consonant, 13..16),
+                (TokenKind::VowelSign, 16..19),
+                (TokenKind::VedicAccent, 19..22),
+                (TokenKind::Avagraha, 22..25),
+                (TokenKind::Visarga, 25..28),
+                (TokenKind::Numeral, 29..32),
+                (TokenKind::Daṇḍa, 32..35),
+                (TokenKind::DoubleDaṇḍa, 35..38),
+                (TokenKind::Candrabindu, 39..42),
+                (TokenKind::Abbreviation, 43..46),
+                (TokenKind::Other, 47..48),
+            ],
+        ),
+        (
+            tokenize_uast,
+            "kṛṣṇaṃ ca? /xy/",
+            vec![
+                (TokenKind::Consonant, 0..1),
+                (TokenKind::VowelSign, 1..4),
+                (TokenKind::Consonant, 4..7),
+                (TokenKind::Halanta, 7..7),
+                (TokenKind::Consonant, 7..11),
+                (TokenKind::Anusvāra, 11..14),
+                (TokenKind::Consonant, 15..17),
+                (TokenKind::Other, 17..18),
+                (TokenKind::Other, 19..23),
+            ],
+        ),
+        // decomposed marks are read as the letters they make up
+        (
+            tokenize_uast,
+            "bhu\u{304}r kr\u{323}s\u{323}n\u{323}a",
+            vec![
+                (TokenKind::Consonant, 0..2),
+                (TokenKind::VowelSign, 2..5),
+                (TokenKind::Consonant, 5..6),
+                (TokenKind::Halanta, 6..6),
+                (TokenKind::Consonant, 7..8),
+                (TokenKind::VowelSign, 8..11),
+                (TokenKind::Consonant, 11..14),
+                (TokenKind::Halanta, 14..14),
+                (TokenKind::Consonant, 14..18),
+            ],
+        ),
+    ];
+
+    for (f, k, v) in arr {
+        assert_eq!(
+            v,
+            f(k).into_iter()
+                .map(|v| (v.kind, v.span))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    let letters = |s| {
+        tokenize_uast(s)
+            .into_iter()
+            .map(|v| (v.kind, v.letter))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(letters("bhū"), letters("bhu\u{304}"));
+}
+
+#[cfg(feature = "itrans")]
+#[test]
+fn test_itrans() {
+    use crate::{Scheme, itrans_to_devanāgarī, transliterate};
+
+    let arr = vec![
+        (
+            "ma~NgalaM bhagavAnviShNurma~NgalaM garuDadhvajaH | ma~NgalaM puNDarIkAkSho ma~NgalAyatanaM hariH ||",
+            "मङ्गलं भगवान्विष्णुर्मङ्गलं गरुडध्वजः । मङ्गलं पुण्डरीकाक्षो मङ्गलायतनं हरिः ॥",
+        ),
+        (
+            "agnimILe purohitaM yaj~nasya devamRRitvijam | hotAraM ratnadhAtamam ||",
+            "अग्निमीळे पुरोहितं यज्ञस्य देवमृत्विजम् । होतारं रत्नधातमम् ॥",
+        ),
+        (
+            "shivaH shiShyaH kLLiptaH LLikAraH LLIkAraH pitRRIn",
+            "शिवः शिष्यः कॢप्तः ऌकारः ॡकारः पितॄन्",
+        ),
+        (
+            "aishvaryam auShadham kaiTabhaH gauDaH sa devA.N eha .agne OM 1947",
+            "ऐश्वर्यम् औषधम् कैटभः गौडः स देवाँ एह ऽग्ने ॐ १९४७",
+        ),
+        ("ChAyA chandraH jhaShaH", "छाया चन्द्रः झषः"),
+        ("pra{}uga k{}ha", "प्रउग क्ह"),
+    ];
+
+    for (itrans, dn) in arr {
+        assert_eq!(dn, itrans_to_devanāgarī(itrans));
+        assert_eq!(
+            itrans,
+            transliterate(dn, Scheme::Devanāgarī, Scheme::Itrans)
+        );
+    }
+
+    // every spelling of a letter reads the same
+    let arr = vec![
+        ("rAma", "raama"),
+        ("gItA", "geetaa"),
+        ("rUpa", "ruupa"),
+        ("kRRiShNa", "kR^iShNa"),
+        ("pitRRIn", "pitR^In"),
+        ("kLLipta", "kL^ipta"),
+        ("ma~Ngala", "maN^gala"),
+        ("pa~ncha", "paJNcha"),
+        ("Chatra", "chhatra"),
+        ("ShaT", "shhaT"),
+        ("ShaT", "SaT"),
+        ("chandra", "candra"),
+        ("vana", "wana"),
+        ("agnimILe", "agnimIlde"),
+        ("saMskRRita", "sa.nskRRita"),
+        ("saMskRRita", "sa.mskRRita"),
+        ("OM", "AUM"),
+        ("kShatriya", "xatriya"),
+        ("j~nAna", "GYAna"),
+        ("j~nAna", "dnyAna"),
+    ];
+    for (a, b) in arr {
+        assert_eq!(itrans_to_devanāgarī(a), itrans_to_devanāgarī(b), "{b}");
+    }
+
+    // `{}` keeps letters apart, `.h` is a halanta and ITRANS is case sensitive
+    assert_eq!("अइ ऐ", itrans_to_devanāgarī("a{}i ai"));
+    assert_eq!("क्ह ख", itrans_to_devanāgarī("k{}ha kha"));
+    assert_eq!("क् क्", itrans_to_devanāgarī("k.h k"));
+    assert_eq!("ष स", itrans_to_devanāgarī("Sa sa"));
+    #[cfg(feature = "iast")]
+    assert_eq!(
+        "rAmaH",
+        transliterate("rāmaḥ", Scheme::Iast, Scheme::Itrans)
+    );
+}
+
+#[cfg(feature = "velthuis")]
+#[test]
+fn test_velthuis() {
+    use crate::{devanāgarī_to_velthuis, velthuis_to_devanāgarī};
+
+    let arr = vec![
+        (
+            "ma\"ngala.m bhagavaanvi.s.nurma\"ngala.m garu.dadhvaja.h . ma\"ngala.m pu.n.dariikaak.so ma\"ngalaayatana.m hari.h ..",
+            "मङ्गलं भगवान्विष्णुर्मङ्गलं गरुडध्वजः । मङ्गलं पुण्डरीकाक्षो मङ्गलायतनं हरिः ॥",
+        ),
+        (
+            "\"siva.h \"si.sya.h k.r.s.na.h k.lpta.h .lkaara.h .llkaara.h pit.rrn",
+            "शिवः शिष्यः कृष्णः कॢप्तः ऌकारः ॡकारः पितॄन्",
+        ),
+        (
+            "ai\"svaryam au.sadham kai.tabha.h gau.da.h sa devaa/ eha .agne pa~nca 1947",
+            "ऐश्वर्यम् औषधम् कैटभः गौडः स देवाँ एह ऽग्ने पञ्च १९४७",
+        ),
+        (
+            ".thakkura.h .dhakkaa chaayaa jha.sa.h",
+            "ठक्कुरः ढक्का छाया झषः",
+        ),
+    ];
+
+    for (velthuis, dn) in arr {
+        assert_eq!(dn, velthuis_to_devanāgarī(velthuis));
+        assert_eq!(velthuis, devanāgarī_to_velthuis(dn));
+    }
+
+    // `&` is a halanta of its own, and ॐ is written as is
+    assert_eq!("क् क्", velthuis_to_devanāgarī("k& k"));
+    assert_eq!("o.mkaara.h ॐ", devanāgarī_to_velthuis("ओंकारः ॐ"));
+    #[cfg(feature = "iast")]
+    assert_eq!(
+        "raama.h",
+        crate::transliterate("rāmaḥ", crate::Scheme::Iast, crate::Scheme::Velthuis)
+    );
+}
+
+#[cfg(feature = "iso")]
+#[test]
+fn test_iso() {
+    use crate::{
+        Options, Scheme, Unknown, devanāgarī_to_iso, iso_to_devanāgarī, transliterate,
+        transliterate_with, try_transliterate,
+    };
+
+    let arr = vec![
+        (
+            "maṅgalaṁ bhagavānviṣṇurmaṅgalaṁ garuḍadhvajaḥ. maṅgalaṁ puṇḍarīkākṣō maṅgalāyatanaṁ hariḥ..",
+            "मङ्गलं भगवान्विष्णुर्मङ्गलं गरुडध्वजः। मङ्गलं पुण्डरीकाक्षो मङ्गलायतनं हरिः॥",
+        ),
+        (
+            "kr̥ṣṇaḥ kl̥ptaḥ l̥kāraḥ l̥̄kāraḥ pitr̥̄n agnimīḷē",
+            "कृष्णः कॢप्तः ऌकारः ॡकारः पितॄन् अग्निमीळे",
+        ),
+        (
+            "aiśvaryam auṣadham ēka ōṣadhi sa dēvām̐ ēha ’gnē 1947",
+            "ऐश्वर्यम् औषधम् एक ओषधि स देवाँ एह ऽग्ने १९४७",
+        ),
+        // the short e and o only ISO 15919 writes
+        ("kompu ceyya", "कॊम्पु चॆय्य"),
+        // `:` keeps the vowels of a sequence and the letters of a cluster apart
+        ("a:i ka:u pra:uga ak:har ad:hva", "अइ कउ प्रउग अक्हर् अद्ह्व"),
+        // the letters of the other Indic languages, with the nukta apart as in NFC
+        (
+            "qilā ḵẖān ġazal ziṁdagī paṛhā saṛak fārasī ẏa ṛ:h",
+            "क\u{93c}िला ख\u{93c}ान् ग\u{93c}ज\u{93c}ल् ज\u{93c}िंदगी पढ\u{93c}ा सड\u{93c}क् फ\u{93c}ारसी य\u{93c} ड\u{93c}्ह्",
+        ),
+        ("ṉāṭu ṟa ḻa", "ऩाटु ऱ ऴ"),
+        ("ḍôkṭar bêṭ ê ô", "डॉक्टर् बॅट् ऍ ऑ"),
+    ];
+
+    for (iso, dn) in arr {
+        assert_eq!(iso, devanāgarī_to_iso(dn));
+        assert_eq!(dn, iso_to_devanāgarī(iso));
+    }
+
+    // ગુજરાતી goes both ways as well
+    #[cfg(feature = "gu")]
+    assert_eq!(
+        "kr̥ṣṇaḥ dēvaṁ",
+        transliterate("કૃષ્ણઃ દેવં", Scheme::Gujarātī, Scheme::Iso15919)
+    );
+    #[cfg(feature = "gu")]
+    assert_eq!(
+        "કૃષ્ણઃ દેવં",
+        transliterate("kr̥ṣṇaḥ dēvaṁ", Scheme::Iso15919, Scheme::Gujarātī)
+    );
+
+    // the anusvāra of IAST and capitals are read, and a decomposed long r̥̄ is put in order
+    assert_eq!(
+        "संस्कृतम् राम",
+        transliterate("saṃskr̥tam Rāma", Scheme::Iso15919, Scheme::Devanāgarī)
+    );
+    assert_eq!(
+        "पितॄन्",
+        transliterate("pitr\u{304}\u{325}n", Scheme::Iso15919, Scheme::Devanāgarī)
+    );
+    // IAST cannot write the short e, but ISO 15919 tells it apart
+    #[cfg(feature = "iast")]
+    assert_eq!(
+        "dēva",
+        transliterate("deva", Scheme::Iast, Scheme::Iso15919)
+    );
+
+    // the nukta letters read the same composed, and their letters decomposed
+    assert_eq!("qilā ḵẖān", devanāgarī_to_iso("\u{958}िला \u{959}ान्"));
+    assert_eq!(
+        "ख\u{93c}ान्",
+        transliterate("k\u{331}h\u{331}ān", Scheme::Iso15919, Scheme::Devanāgarī)
+    );
+
+    // a `:` that keeps nothing apart is punctuation, which ISO 15919 does not read
+    let pass = Options {
+        unknown: Unknown::PassThrough,
+        ..Default::default()
+    };
+    assert_eq!(
+        "रामः: वनं",
+        transliterate_with("rāmaḥ: vanaṁ", Scheme::Iso15919, Scheme::Devanāgarī, &pass)
+    );
+    let err = try_transliterate("rāmaḥ: vanam", Scheme::Iso15919, Scheme::Devanāgarī).unwrap_err();
+    assert_eq!(
+        vec![(":", 8)],
+        err.unmappable
+            .iter()
+            .map(|v| (v.text.as_str(), v.byte_offset))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        "अ:ब",
+        transliterate_with("a:ba", Scheme::Iso15919, Scheme::Devanāgarī, &pass)
+    );
+}
+
+#[cfg(feature = "wx")]
+#[test]
+fn test_wx() {
+    use crate::{Scheme, devanāgarī_to_wx, transliterate, wx_to_devanāgarī};
+
+    let arr = vec![
+        (
+            "maMgalaM BagavAnviRNurmaMgalaM garudaXvajaH . maMgalaM puNdarIkAkRo maMgalAyawanaM hariH ..",
+            "मंगलं भगवान्विष्णुर्मंगलं गरुडध्वजः । मंगलं पुण्डरीकाक्षो मंगलायतनं हरिः ॥",
+        ),
+        (
+            "SivaH SiRyaH kqRNaH kLpwaH LkAraH piwQn aFjaliH afgam",
+            "शिवः शिष्यः कृष्णः कॢप्तः ऌकारः पितॄन् अञ्जलिः अङ्गम्",
+        ),
+        (
+            "ESvaryam ORaXam kEtaBaH gOdaH sa xevAz eha 'gne 1947",
+            "ऐश्वर्यम् औषधम् कैटभः गौडः स देवाँ एह ऽग्ने १९४७",
+        ),
+        ("TakkuraH DakkA CAyA JaRaH", "ठक्कुरः ढक्का छाया झषः"),
+    ];
+
+    for (wx, dn) in arr {
+        assert_eq!(wx, devanāgarī_to_wx(dn));
+        assert_eq!(dn, wx_to_devanāgarī(wx));
+    }
+
+    // WX is case sensitive, and what it cannot write is left out
+    assert_eq!(
+        "w W x X",
+        transliterate("त् थ् द् ध्", Scheme::Devanāgarī, Scheme::Wx)
+    );
+    assert_eq!(
+        "agnimI",
+        transliterate("अग्निमीळे", Scheme::Devanāgarī, Scheme::Wx)
+    );
+    #[cfg(feature = "slp")]
+    assert_eq!(
+        "Bargo xevasya",
+        transliterate("Bargo devasya", Scheme::Slp1, Scheme::Wx)
+    );
+}
+
+#[cfg(all(
+    feature = "gu",
+    feature = "uast",
+    feature = "slp",
+    feature = "hk",
+    feature = "itrans",
+    feature = "velthuis",
+    feature = "iso",
+    feature = "wx"
+))]
+#[test]
+fn test_tables() {
+    use crate::{
+        Scheme,
+        tables::{self, Category},
+        transliterate,
+    };
+
+    // every vowel, consonant and misc letter reads back as its देवनागरी
+    for scheme in [
+        Scheme::Gujarātī,
+        Scheme::Iast,
+        Scheme::UastIo,
+        Scheme::Slp1,
+        Scheme::HarvardKyoto,
+        Scheme::Itrans,
+        Scheme::Velthuis,
+        Scheme::Iso15919,
+        Scheme::Wx,
+    ] {
+        for v in tables::entries(scheme.clone()) {
+            let dn = match (v.category, &scheme) {
+                (Category::Consonant, Scheme::Gujarātī) => v.devanāgarī.to_string(),
+                (Category::Consonant, _) => format!("{}्", v.devanāgarī),
+                (Category::Vowel | Category::Misc, _) => v.devanāgarī.to_string(),
+                _ => continue,
+            };
+            assert_eq!(
+                // the nukta letters are read in NFC
+                transliterate(&dn, Scheme::Devanāgarī, Scheme::Devanāgarī),
+                transliterate(&v.text, scheme.clone(), Scheme::Devanāgarī),
+                "{v:?}"
+            );
+        }
+    }
+
+    let count = |scheme, category| {
+        tables::entries(scheme)
+            .iter()
+            .filter(|v| v.category == category)
+            .count()
+    };
+    assert_eq!(18, count(Scheme::Devanāgarī, Category::Vowel));
+    assert_eq!(45, count(Scheme::Iso15919, Category::Consonant));
+    assert_eq!(13, count(Scheme::Iast, Category::VowelSign));
+    assert_eq!(34, count(Scheme::Gujarātī, Category::Consonant));
+    assert_eq!(13, count(Scheme::Slp1, Category::Misc));
+    assert_eq!(20, count(Scheme::Devanāgarī, Category::Special));
+    assert_eq!(3, count(Scheme::Iast, Category::Special));
+
+    let escapes = tables::entries(Scheme::UastIo)
+        .into_iter()
+        .filter(|v| v.category == Category::Escape)
+        .map(|v| (v.text, v.devanāgarī))
+        .collect::<Vec<_>>();
+    assert!(escapes.contains(&("/a/".to_string(), 'आ')));
+    assert!(escapes.contains(&("/a/".to_string(), 'ा')));
+    assert!(escapes.contains(&("/''/".to_string(), '᳚')));
+    assert!(escapes.contains(&("/om/".to_string(), 'ॐ')));
+    assert!(escapes.contains(&("/nl/".to_string(), 'ण')));
+    // ā, ī, ū, ṛ, ṝ, ḷ and ḹ are both vowels and vowel signs
+    assert_eq!(34 + 7, escapes.len());
+}
+
+#[cfg(all(feature = "gu", feature = "slp"))]
+fn definition(scheme: crate::Scheme) -> crate::Definition {
+    use crate::{Definition, Spelling, tables};
+
+    let mut def = Definition {
+        name: scheme.to_string(),
+        ..Default::default()
+    };
+
+    for v in tables::entries(scheme) {
+        let map = match v.category {
+            tables::Category::Vowel => &mut def.vowels,
+            tables::Category::VowelSign => &mut def.vowel_signs,
+            tables::Category::Consonant => &mut def.consonants,
+            tables::Category::Misc => &mut def.misc,
+            tables::Category::Special => &mut def.specials,
+            tables::Category::Escape => continue,
+        };
+        map.insert(v.devanāgarī.to_string(), Spelling::One(v.text));
+    }
+
+    def
+}
+
+#[cfg(all(feature = "gu", feature = "slp"))]
+#[test]
+fn test_custom() {
+    use crate::{
+        CustomScheme, Definition, DefinitionError, Options, Scheme, ScriptKind, Spelling,
+        transliterate, transliterate_with_alignment,
+    };
+    use std::sync::Arc;
+
+    // the built-in schemes read from their own tables give the same results
+    let dn = "भूर्भुवः स्वः तत्सवितुर्वरेण्यं भर्गो देवस्य धीमहि। धियो यो नः प्रचोदयात्॥ १०८";
+    for (scheme, kind) in [
+        (Scheme::Iast, ScriptKind::Roman),
+        (Scheme::Slp1, ScriptKind::Roman),
+        (Scheme::Gujarātī, ScriptKind::Brahmic),
+    ] {
+        let def = Definition {
+            kind,
+            ..definition(scheme.clone())
+        };
+        let custom = Scheme::Custom(Arc::new(CustomScheme::new(def).unwrap()));
+        let s = transliterate(dn, Scheme::Devanāgarī, scheme.clone());
+
+        assert_eq!(s, transliterate(dn, Scheme::Devanāgarī, custom.clone()));
+        assert_eq!(
+            transliterate(&s, scheme.clone(), Scheme::Devanāgarī),
+            transliterate(&s, custom.clone(), Scheme::Devanāgarī)
+        );
+        assert_eq!(s, transliterate(&s, custom, scheme));
+    }
+
+    // an in-house romanisation with alternate spellings and escapes
+    let mut def = definition(Scheme::Iast);
+    def.name = "In-house".to_string();
+    let one = |v: &str| Spelling::One(v.to_string());
+    for (k, v) in [("श", "sh"), ("ष", "S"), ("ञ", "ny")] {
+        def.consonants.insert(k.to_string(), one(v));
+    }
+    def.specials.insert("ं".to_string(), one("M"));
+
+    let aa = Spelling::Alternatives(vec!["aa".to_string(), "ā".to_string()]);
+    def.vowels.insert("आ".to_string(), aa.clone());
+    def.vowel_signs.insert("ा".to_string(), aa);
+    def.escapes.insert("{om}".to_string(), "oM".to_string());
+    let custom = Scheme::Custom(Arc::new(CustomScheme::new(def).unwrap()));
+
+    assert_eq!("In-house", custom.to_string());
+    assert_eq!(
+        "shaantiḥ shaantiM",
+        transliterate("शान्तिः शान्तिं", Scheme::Devanāgarī, custom.clone())
+    );
+    assert_eq!(
+        "शान्तिः शान्तिः कृष्णं ओं",
+        transliterate(
+            "shaantiḥ shāntiḥ kṛSṇaM {om}",
+            custom.clone(),
+            Scheme::Devanāgarī
+        )
+    );
+    assert_eq!("ñ", transliterate("ny", custom.clone(), Scheme::Iast));
+
+    let res = transliterate_with_alignment("shaa", custom, Scheme::Devanāgarī, &Options::default());
+    assert_eq!("शा", res.text);
+    assert_eq!(
+        vec![0..2, 2..4],
+        res.alignment
+            .iter()
+            .map(|v| v.source.clone())
+            .collect::<Vec<_>>()
+    );
+
+    let def = |k: &str, v: &str| Definition {
+        name: "Broken".to_string(),
+        vowels: [(k.to_string(), Spelling::One(v.to_string()))].into(),
+        ..Default::default()
+    };
+    assert_eq!(
+        Err(DefinitionError::NotOneCharacter("अअ".to_string())),
+        CustomScheme::new(def("अअ", "a"))
+    );
+    assert_eq!(
+        Err(DefinitionError::InvalidSpelling("a a".to_string())),
+        CustomScheme::new(def("अ", "a a"))
+    );
+    assert_eq!(
+        Err(DefinitionError::MissingInherentVowel),
+        CustomScheme::new(def("आ", "aa"))
+    );
+}
+
+#[test]
+fn test_custom_alone() {
+    use crate::{CustomScheme, Definition, Scheme, SchemeDefinition, Spelling, transliterate};
+    use std::sync::Arc;
+
+    // देवनागरी and custom schemes need no scheme feature
+    let one = |v: &str| Spelling::One(v.to_string());
+    let def = Definition {
+        name: "Tiny".to_string(),
+        vowels: [("अ".to_string(), one("a")), ("इ".to_string(), one("i"))].into(),
+        vowel_signs: [("ि".to_string(), one("i"))].into(),
+        consonants: [("न".to_string(), one("n")), ("म".to_string(), one("m"))].into(),
+        specials: [("्".to_string(), one("^"))].into(),
+        ..Default::default()
+    };
+    let tiny = CustomScheme::new(def).unwrap();
+    assert!(tiny.index().is_some());
+    let tiny = Scheme::Custom(Arc::new(tiny));
+
+    assert_eq!(
+        "नमि इन्",
+        transliterate("nami in", tiny.clone(), Scheme::Devanāgarī)
+    );
+    assert_eq!("nami in", transliterate("नमि इन्", Scheme::Devanāgarī, tiny));
+    assert_eq!(
+        "नमि",
+        transliterate("नमि", Scheme::Devanāgarī, Scheme::Devanāgarī)
+    );
+}
+
+#[cfg(all(feature = "toml", feature = "json", feature = "slp"))]
+#[test]
+fn test_custom_files() {
+    use crate::{CustomScheme, DefinitionError, Scheme, transliterate};
+    use std::sync::Arc;
+
+    let toml = r#"
+        name = "Simple"
+        kind = "roman"
+
+        [vowels]
+        "अ" = "a"
+        "आ" = ["aa", "A"]
+
+        [vowel_signs]
+        "ा" = ["aa", "A"]
+
+        [consonants]
+        "क" = "k"
+        "म" = "m"
+        "ल" = "l"
+
+        [escapes]
+        "{A}" = "aa"
+    "#;
+    let json = r#"{
+        "name": "Simple",
+        "vowels": { "अ": "a", "आ": ["aa", "A"] },
+        "vowel_signs": { "ा": ["aa", "A"] },
+        "consonants": { "क": "k", "म": "m", "ल": "l" },
+        "escapes": { "{A}": "aa" }
+    }"#;
+
+    let a = CustomScheme::from_toml(toml).unwrap();
+    assert_eq!(a, CustomScheme::from_json(json).unwrap());
+
+    let simple = Scheme::Custom(Arc::new(a));
+    assert_eq!(
+        "कमला आम",
+        transliterate("kamalaa Ama", simple.clone(), Scheme::Devanāgarī)
+    );
+    assert_eq!(
+        "kamalA",
+        transliterate("kamal{A}", simple.clone(), Scheme::Slp1)
+    );
+    assert_eq!("kamalaa", transliterate("कमला", Scheme::Devanāgarī, simple));
+
+    assert!(matches!(
+        CustomScheme::from_toml("name = 1"),
+        Err(DefinitionError::Syntax(_))
+    ));
+    assert!(matches!(
+        CustomScheme::from_json(r#"{"name": "x", "vowel": {}}"#),
+        Err(DefinitionError::Syntax(_))
+    ));
+}
+
+#[cfg(all(feature = "gu", feature = "iast"))]
+#[test]
+fn test_scheme_definition() {
+    use crate::{
+        Scheme, SchemeDefinition, ScriptKind,
+        tables::{self, Entry},
+        transliterate,
+    };
+    use std::{collections::HashSet, sync::Arc};
+
+    // a script defined by another crate
+    #[derive(Debug)]
+    struct Script(Vec<Entry>);
+
+    impl SchemeDefinition for Script {
+        fn name(&self) -> &str {
+            "Script"
+        }
+
+        fn kind(&self) -> ScriptKind {
+            ScriptKind::Brahmic
+        }
+
+        fn entries(&self) -> &[Entry] {
+            &self.0
+        }
+    }
+
+    let script = || Scheme::Custom(Arc::new(Script(tables::entries(Scheme::Gujarātī))));
+    let (a, b) = (script(), script());
+
+    let dn = "ॐ भूर्भुवः स्वः तत्सवितुर्वरेण्यं भर्गो देवस्य धीमहि।";
+    let gu = transliterate(dn, Scheme::Devanāgarī, Scheme::Gujarātī);
+    assert_eq!(gu, transliterate(dn, Scheme::Devanāgarī, a.clone()));
+    assert_eq!(dn, transliterate(&gu, a.clone(), Scheme::Devanāgarī));
+    assert_eq!("Script", a.to_string());
+
+    // custom schemes are told apart by the definition they share
+    assert_eq!(a, a.clone());
+    assert_ne!(a, b);
+    assert_ne!(a, Scheme::Gujarātī);
+    assert_eq!(
+        3,
+        [a.clone(), b, a, Scheme::Iast]
+            .into_iter()
+            .collect::<HashSet<_>>()
+            .len()
+    );
+}
+
+#[cfg(all(feature = "gu", feature = "uast", feature = "slp"))]
+#[test]
+fn test_normalization() {
+    use crate::{
+        Normalization, Options, Scheme, Unknown, transliterate, transliterate_with,
+        transliterate_with_alignment, try_transliterate,
+    };
+
+    // decomposed and composed input read the same
+    let arr = vec![
+        (
+            "bhu\u{304}rbhuvah\u{323} svah\u{323}",
+            "bhūrbhuvaḥ svaḥ",
+            Scheme::Iast,
+            Scheme::Devanāgarī,
+        ),
+        (
+            "kr\u{323}s\u{323}n\u{323}am\u{323} s\u{301}a\u{304}ntih\u{323}",
+            "kṛṣṇaṃ śāntiḥ",
+            Scheme::UastIo,
+            Scheme::Devanāgarī,
+        ),
+        // the marks of ṝ in either order, and on ṛ
+        (
+            "pitr\u{323}\u{304}n",
+            "pitṝn",
+            Scheme::Iast,
+            Scheme::Devanāgarī,
+        ),
+        (
+            "pitr\u{304}\u{323}n",
+            "pitṝn",
+            Scheme::Iast,
+            Scheme::Devanāgarī,
+        ),
+        ("pitṛ\u{304}n", "pitṝn", Scheme::Iast, Scheme::Devanāgarī),
+        (
+            "BHU\u{304}H\u{323}",
+            "BHŪḤ",
+            Scheme::UastIo,
+            Scheme::Devanāgarī,
+        ),
+        ("jn\u{303}a\u{304}na", "jñāna", Scheme::Iast, Scheme::Slp1),
+        ("न\u{93c}", "ऩ", Scheme::Devanāgarī, Scheme::Iast),
+        ("\u{958}", "क\u{93c}", Scheme::Devanāgarī, Scheme::Gujarātī),
+    ];
+
+    let pass = Options {
+        unknown: Unknown::PassThrough,
+        ..Default::default()
+    };
+    for (nfd, nfc, from, to) in arr {
+        assert_eq!(
+            transliterate_with(nfc, from.clone(), to.clone(), &pass),
+            transliterate_with(nfd, from, to, &pass)
+        );
+    }
+    assert_eq!(
+        "भूर्भुवः",
+        transliterate("bhu\u{304}rbhuvah\u{323}", Scheme::Iast, Scheme::Devanāgarī)
+    );
+
+    // diagnostics and alignment point into the input as it was given
+    let err =
+        try_transliterate("a\u{304}! ka\u{304}?", Scheme::Iast, Scheme::Devanāgarī).unwrap_err();
+    assert_eq!(
+        vec![("!", 3, 2), ("?", 9, 7)],
+        err.unmappable
+            .iter()
+            .map(|v| (v.text.as_str(), v.byte_offset, v.char_offset))
+            .collect::<Vec<_>>()
+    );
+
+    let res = transliterate_with_alignment(
+        "ka\u{304}s\u{301}ī",
+        Scheme::Iast,
+        Scheme::Devanāgarī,
+        &Options::default(),
+    );
+    assert_eq!("काशी", res.text);
+    assert_eq!(
+        vec![(0..1, 0..1), (1..3, 1..2), (3..5, 2..3), (5..6, 3..4)],
+        res.alignment
+            .iter()
+            .map(|v| (v.source.clone(), v.output.clone()))
+            .collect::<Vec<_>>()
+    );
+
+    // the output in either form
+    let nfd = Options {
+        normalization: Normalization::Nfd,
+        ..Default::default()
+    };
+    for (dn, to, s) in [
+        ("भूः", Scheme::Iast, "bhu\u{304}h\u{323}"),
+        ("पितॄन्", Scheme::UastIo, "pitr\u{323}\u{304}n"),
+        ("शान्तिः", Scheme::Iast, "s\u{301}a\u{304}ntih\u{323}"),
+        ("ऩ", Scheme::Devanāgarī, "न\u{93c}"),
+    ] {
+        assert_eq!(
+            s,
+            transliterate_with(dn, Scheme::Devanāgarī, to.clone(), &nfd)
+        );
+        assert_eq!(
+            transliterate(dn, Scheme::Devanāgarī, to.clone()),
+            transliterate(s, to.clone(), to)
+        );
+    }
+
+    let res = transliterate_with_alignment("भूः", Scheme::Devanāgarī, Scheme::Iast, &nfd);
+    assert_eq!(
+        vec![(0..1, 0..2), (1..2, 2..4), (2..3, 4..6)],
+        res.alignment
+            .iter()
+            .map(|v| (v.source.clone(), v.output.clone()))
+            .collect::<Vec<_>>()
+    );
+
+    // NFC splits the nukta off its letter, and the vowel sign after the nukta is still written
+    for (dn, to, s, passed) in [
+        ("\u{95b}िंदगी", Scheme::Iast, "jiṃdagī", "j\u{93c}iṃdagī"),
+        ("ज\u{93c}िंदगी", Scheme::Iast, "jiṃdagī", "j\u{93c}iṃdagī"),
+        ("\u{95e}ारसी", Scheme::Slp1, "PArasI", "P\u{93c}ArasI"),
+        ("फ\u{93c}ारसी", Scheme::UastIo, "phārasī", "ph\u{93c}ārasī"),
+        #[cfg(feature = "hk")]
+        (
+            "\u{95b}िंदगी",
+            Scheme::HarvardKyoto,
+            "jiMdagI",
+            "j\u{93c}iMdagI",
+        ),
+        #[cfg(feature = "itrans")]
+        ("ज\u{93c}िंदगी", Scheme::Itrans, "jiMdagI", "j\u{93c}iMdagI"),
+        #[cfg(feature = "velthuis")]
+        (
+            "\u{95e}ारसी",
+            Scheme::Velthuis,
+            "phaarasii",
+            "ph\u{93c}aarasii",
+        ),
+        #[cfg(feature = "wx")]
+        ("फ\u{93c}ारसी", Scheme::Wx, "PArasI", "P\u{93c}ArasI"),
+    ] {
+        assert_eq!(s, transliterate(dn, Scheme::Devanāgarī, to.clone()));
+        assert_eq!(
+            passed,
+            transliterate_with(dn, Scheme::Devanāgarī, to.clone(), &pass)
+        );
+
+        let err = try_transliterate(dn, Scheme::Devanāgarī, to).unwrap_err();
+        assert_eq!(1, err.unmappable.len());
+        assert_eq!(0, err.unmappable[0].byte_offset);
+    }
+}
+
+#[cfg(all(
+    feature = "gu",
+    feature = "uast",
+    feature = "slp",
+    feature = "hk",
+    feature = "itrans",
+    feature = "wx"
+))]
+#[test]
+fn test_detect_scheme() {
+    use crate::{Scheme, detect_scheme};
+
+    let arr = vec![
+        ("ॐ भूर्भुवः स्वः", Scheme::Devanāgarī),
+        ("ૐ ભૂર્ભુવઃ સ્વઃ", Scheme::Gujarātī),
+        ("oṃ bhūrbhuvaḥ svaḥ", Scheme::Iast),
+        ("om bhu\u{304}rbhuvah\u{323} svah\u{323}", Scheme::Iast),
+        ("/om/ bh/u/rbhuvah/h/ svah/h/", Scheme::UastIo),
+        ("oM BUrBuvaH svaH", Scheme::Slp1),
+        // without diacritics or capitals
+        ("tatsaviturvarenyam bhargo devasya dhimahi", Scheme::Iast),
+        ("tatsavitur vareRyam", Scheme::Slp1),
+        ("kfzRa", Scheme::Slp1),
+        ("vaisnava", Scheme::Iast),
+        ("vEzRava", Scheme::Slp1),
+        ("Kṛṣṇa", Scheme::Iast),
+        ("भर्गो देवस्य dhīmahi", Scheme::Devanāgarī),
+    ];
+
+    for (text, scheme) in arr {
+        let res = detect_scheme(text);
+        assert_eq!(scheme, res[0].scheme, "{text}: {res:?}");
+        assert!((res.iter().map(|v| v.confidence).sum::<f32>() - 1.0).abs() < 1e-4);
+        assert!(res.windows(2).all(|v| v[0].confidence >= v[1].confidence));
+    }
+
+    let res = detect_scheme("भर्गो dhīmahi");
+    assert_eq!(
+        vec![
+            Scheme::Devanāgarī,
+            Scheme::Iast,
+            Scheme::UastIo,
+            Scheme::Slp1,
+            Scheme::HarvardKyoto,
+            Scheme::Itrans,
+            Scheme::Wx
+        ],
+        res.iter().map(|v| v.scheme.clone()).collect::<Vec<_>>()
+    );
+
+    // Harvard-Kyoto spells aspirates with two letters, which SLP1 would read as two consonants
+    for text in [
+        "maGgalaM bhagavAn viSNur maGgalaM garuDadhvajaH",
+        "dharmakSetre kurukSetre",
+    ] {
+        let res = detect_scheme(text);
+        assert_eq!(Scheme::HarvardKyoto, res[0].scheme, "{text}: {res:?}");
+        assert!(
+            res.iter()
+                .find(|v| v.scheme == Scheme::Slp1)
+                .is_some_and(|v| v.confidence < res[0].confidence)
+        );
+    }
+
+    // without aspirates or diphthongs, Harvard-Kyoto is as likely as SLP1
+    let res = detect_scheme("maGgalaM viSNur");
+    assert!(res[0].confidence < 0.5);
+    assert!(
+        res.iter()
+            .any(|v| v.scheme == Scheme::HarvardKyoto && v.confidence == res[0].confidence)
+    );
+    assert!(detect_scheme(" \n\t").is_empty());
+    assert!(detect_scheme("1, 2, 3...").is_empty());
+}
+
+#[cfg(all(feature = "gu", feature = "iast"))]
+#[test]
+fn test_segments() {
+    use crate::{Options, Scheme, Script, segment, transliterate_segments};
+
+    let arr = vec![
+        (
+            "ધીમહિ। ધિયો, then (કદાચન॥)",
+            vec![
+                (Script::Gujarātī, "ધીમહિ। ધિયો"),
+                (Script::Other, ", "),
+                (Script::Latin, "then"),
+                (Script::Other, " ("),
+                (Script::Gujarātī, "કદાચન॥"),
+                (Script::Other, ")"),
+            ],
+        ),
+        (
+            "see /om/ and bhu\u{304}h\u{323}, not and/or",
+            vec![
+                (Script::Latin, "see"),
+                (Script::Other, " "),
+                (Script::Iast, "/om/"),
+                (Script::Other, " "),
+                (Script::Latin, "and"),
+                (Script::Other, " "),
+                (Script::Iast, "bhu\u{304}h\u{323}"),
+                (Script::Other, ", "),
+                (Script::Latin, "not and/or"),
+            ],
+        ),
+        (
+            "\tदेव-दत्तः\n\nsaw Kṛṣṇa-Arjuna.",
+            vec![
+                (Script::Other, "\t"),
+                (Script::Devanāgarī, "देव-दत्तः"),
+                (Script::Other, "\n\n"),
+                (Script::Latin, "saw"),
+                (Script::Other, " "),
+                (Script::Iast, "Kṛṣṇa-Arjuna"),
+                (Script::Other, "."),
+            ],
+        ),
+        ("", vec![]),
+    ];
+
+    for (text, segments) in arr {
+        let res = segment(text);
+        assert_eq!(
+            segments,
+            res.iter()
+                .map(|v| (v.script, &text[v.span.clone()]))
+                .collect::<Vec<_>>()
+        );
+        assert!(res.windows(2).all(|v| v[0].span.end == v[1].span.start));
+    }
+
+    let opts = Options::default();
+    let s = "Commentary: भर्गो देवस्य (bhargo devasya dhīmahi) — ધીમહિ।\n";
+    for (from, to, res) in [
+        (
+            Scheme::Devanāgarī,
+            Scheme::Iast,
+            "Commentary: bhargo devasya (bhargo devasya dhīmahi) — ધીમહિ।\n",
+        ),
+        (
+            Scheme::Iast,
+            Scheme::Devanāgarī,
+            "Commentary: भर्गो देवस्य (भर्गो देवस्य धीमहि) — ધીમહિ।\n",
+        ),
+        (
+            Scheme::Gujarātī,
+            Scheme::Devanāgarī,
+            "Commentary: भर्गो देवस्य (bhargo devasya dhīmahi) — धीमहि।\n",
+        ),
+        (Scheme::Iast, Scheme::Iast, s),
+    ] {
+        assert_eq!(res, transliterate_segments(s, from, to, &opts));
+    }
+
+    let s = "Plain English, with no Sanskrit at all.\n";
+    assert_eq!(
+        s,
+        transliterate_segments(s, Scheme::Iast, Scheme::Devanāgarī, &opts)
+    );
+
+    // a citation is taken whole, but not the English words next to it. a word that could not be
+    // Sanskrit, like `savitur` without its sandhi, keeps the words before it out as well.
+    for (s, res) in [
+        ("bhargo devasya dhīmahi", "भर्गो देवस्य धीमहि"),
+        (
+            "It says bhargo devasya dhīmahi, it means.",
+            "It says bhargo devasya धीमहि, it means.",
+        ),
+        ("tat savitur vareṇyam", "tat savitur वरेण्यम्"),
+    ] {
+        assert_eq!(
+            res,
+            transliterate_segments(s, Scheme::Iast, Scheme::Devanāgarī, &opts)
+        );
+    }
+
+    // the other Roman schemes only read words that plain English does not have
+    #[allow(unused_mut)]
+    let mut arr = vec![];
+    #[cfg(feature = "slp")]
+    arr.extend([
+        (
+            Scheme::Slp1,
+            "The verse Bargo devasya means this.",
+            "The verse Bargo devasya means this.",
+        ),
+        (
+            Scheme::Slp1,
+            "(tat saviturvareRyam BargaH devasya)",
+            "(तत् सवितुर्वरेण्यम् भर्गः देवस्य)",
+        ),
+    ]);
+    #[cfg(feature = "hk")]
+    arr.push((
+        Scheme::HarvardKyoto,
+        "The NASA says dharmakSetre kurukSetre, they write.",
+        "The NASA says धर्मक्षेत्रे कुरुक्षेत्रे, they write.",
+    ));
+    #[cfg(feature = "velthuis")]
+    arr.push((
+        Scheme::Velthuis,
+        "Then k.r.s.na said: don't.",
+        "Then कृष्ण said: don't.",
+    ));
+    #[cfg(feature = "iso")]
+    arr.push((
+        Scheme::Iso15919,
+        "The sūtra reads kr̥ṣṇa dēva today.",
+        "The सूत्र reads कृष्ण देव today.",
+    ));
+    for (from, s, res) in arr {
+        assert_eq!(
+            res,
+            transliterate_segments(s, from.clone(), Scheme::Devanāgarī, &opts),
+            "{from}"
+        );
+    }
+}
+
+#[cfg(all(feature = "gu", feature = "slp"))]
+#[test]
+fn test_text() {
+    use crate::{
+        Scheme,
+        text::{Devanāgarī, Gujarātī, Iast, Slp1},
+    };
+
+    let dn = Devanāgarī::new("भूर्भुवः स्वः॥").unwrap();
+    let gu = Gujarātī::try_from(&dn).unwrap();
+    let iast = Iast::try_from(&dn).unwrap();
+    let slp = Slp1::try_from(&iast).unwrap();
+
+    assert_eq!("ભૂર્ભુવઃ સ્વઃ॥", gu.as_str());
+    assert_eq!("bhūrbhuvaḥ svaḥ..", &*iast);
+    assert_eq!("BUrBuvaH svaH..", slp.to_string());
+    assert_eq!(dn, Devanāgarī::from(gu.clone()));
+    assert_eq!(dn, Devanāgarī::from(&iast));
+    assert_eq!(iast, Iast::try_from(slp.clone()).unwrap());
+    assert_eq!(gu, Gujarātī::try_from(slp).unwrap());
+    assert_eq!(
+        "भूर्भुवः स्वः॥",
+        String::from(Devanāgarī::try_from(dn.into_string()).unwrap())
+    );
+
+    // a letter the target cannot write is an error rather than left out
+    let dn = Devanāgarī::new("ॐ स॑वि॒तुः॰ ऎ").unwrap();
+    let err = Slp1::try_from(&dn).unwrap_err();
+    assert_eq!(Scheme::Devanāgarī, err.scheme);
+    assert_eq!(
+        vec!["ॐ", "\u{951}", "\u{952}", "॰", "ऎ"],
+        err.unmappable
+            .iter()
+            .map(|v| v.text.as_str())
+            .collect::<Vec<_>>()
+    );
+    assert!(Iast::try_from(dn).is_err());
+
+    let arr = [
+        (Devanāgarī::new("देव x").map(|_| ()), vec![(10, 4, "x")]),
+        (Devanāgarī::new("अ॒ग्निमी॑ळे").map(|_| ()), vec![]),
+        (
+            Gujarātī::new("દેવ दे").map(|_| ()),
+            vec![(10, 4, "द"), (13, 5, "े")],
+        ),
+        (
+            Iast::new("de/v/a ā").map(|_| ()),
+            vec![(2, 2, "/"), (4, 4, "/")],
+        ),
+        (Iast::new("deva").map(|_| ()), vec![]),
+        (Slp1::new("devá").map(|_| ()), vec![(3, 3, "á")]),
+        (
+            "de\u{301}va".parse::<Slp1>().map(|_| ()),
+            vec![(1, 1, "e\u{301}")],
+        ),
+    ];
+    for (res, unmappable) in arr {
+        assert_eq!(
+            unmappable
+                .into_iter()
+                .map(|(i, j, k)| (i, j, k.to_string()))
+                .collect::<Vec<_>>(),
+            res.err()
+                .map(|v| v.unmappable)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|v| (v.byte_offset, v.char_offset, v.text))
+                .collect::<Vec<_>>()
+        );
+    }
+}
+
+#[cfg(all(feature = "gu", feature = "uast", feature = "slp"))]
+#[test]
+fn test_display() {
+    use crate::{
+        Scheme, devanāgarī_to_gujarātī, devanāgarī_to_iast,
+        display::{AsDevanāgarī, AsGujarātī, AsIast, AsSlp1, Transliterated},
+        transliterate, uast_to_devanāgarī,
+    };
+    use core::fmt::Write;
+
+    let s =
+        "/om/ bhūrbhuvaḥ svaḥ tatsaviturvareṇyaṃ bhargo devasya dhīmahi. dhiyo yo naḥ pracodayāt..";
+    let dn = uast_to_devanāgarī(s);
+    assert_eq!(dn, AsDevanāgarī(s).to_string());
+    assert_eq!(devanāgarī_to_iast(&dn), AsIast(&dn).to_string());
+    assert_eq!(devanāgarī_to_gujarātī(&dn), AsGujarātī(&dn).to_string());
+    assert_eq!(
+        transliterate(&dn, Scheme::Devanāgarī, Scheme::Slp1),
+        AsSlp1(&dn).to_string()
+    );
+
+    let schemes = [
+        Scheme::Devanāgarī,
+        Scheme::Gujarātī,
+        Scheme::Iast,
+        Scheme::Slp1,
+        Scheme::UastIo,
+    ];
+    for from in &schemes {
+        let text = transliterate(&dn, Scheme::Devanāgarī, from.clone());
+        for to in &schemes {
+            let (from, to) = (from.clone(), to.clone());
+            let mut res = String::from("> ");
+            write!(
+                res,
+                "{}|",
+                Transliterated::new(&text, from.clone(), to.clone())
+            )
+            .unwrap();
+            assert_eq!(format!("> {}|", transliterate(&text, from, to)), res);
+        }
+    }
+}
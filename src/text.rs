@@ -0,0 +1,258 @@
+//! Strings that are known to be written in one scheme
+//!
+//! Each type checks its text on construction, so a function that takes a [`Devanāgarī`] cannot be
+//! handed IAST by mistake. It converts to the others with the same converters as
+//! [`try_transliterate`](crate::try_transliterate), failing on any letter the target cannot
+//! write. Only [`Devanāgarī`], which writes every letter, converts with [`From`].
+//!
+//! ```
+//! use uast::text::{Devanāgarī, Gujarātī, Iast, Slp1};
+//!
+//! let dn = Devanāgarī::new("भर्गो देवस्य धीमहि।").unwrap();
+//! assert_eq!("bhargo devasya dhīmahi.", Iast::try_from(&dn).unwrap().as_str());
+//! assert_eq!("Bargo devasya DImahi.", Slp1::try_from(&dn).unwrap().as_str());
+//! let gu = Gujarātī::try_from(dn).unwrap();
+//! assert_eq!("ભર્ગો દેવસ્ય ધીમહિ।", gu.as_str());
+//! assert_eq!("भर्गो देवस्य धीमहि।", Devanāgarī::from(gu).as_str());
+//!
+//! let dn = Devanāgarī::new("स॑वि॒तुः").unwrap();
+//! assert_eq!("॑", Slp1::try_from(&dn).unwrap_err().unmappable[0].text);
+//!
+//! let err = Devanāgarī::new("bhargo").unwrap_err();
+//! assert_eq!(6, err.unmappable.len());
+//! assert!("dhīmahi".parse::<Iast>().is_ok());
+//! ```
+
+use crate::{
+    DiagnosticKind, Options, Scheme, TokenKind, TransliterationError, Unmappable, dn,
+    scheme::convert,
+    utils::{Context, Sink},
+};
+#[cfg(any(feature = "gu", feature = "iast"))]
+use crate::{transliterate, try_transliterate};
+use alloc::{string::String, vec::Vec};
+use core::{fmt, ops::Deref, str::FromStr};
+
+fn unmappable((j, (i, c)): (usize, (usize, char))) -> Unmappable {
+    Unmappable {
+        byte_offset: i,
+        char_offset: j,
+        text: c.into(),
+    }
+}
+
+// every character of `text` that the reader of `scheme` does not know
+fn unknown(text: &str, scheme: Scheme) -> Vec<Unmappable> {
+    // IAST is read along with the UAST-IO escapes, which are not IAST
+    #[cfg(feature = "iast")]
+    let escapes = scheme == Scheme::Iast;
+    #[cfg(not(feature = "iast"))]
+    let escapes = false;
+
+    let mut ctx = Context::new(Options::default(), scheme, Scheme::Devanāgarī);
+    ctx.collect = true;
+    convert(text, &mut ctx, &mut Sink).expect("nothing is written");
+
+    let mut res = ctx
+        .take_diagnostics(text)
+        .into_iter()
+        .filter(|v| match v.kind {
+            DiagnosticKind::Unknown(_) => true,
+            DiagnosticKind::UnknownEscape => !escapes,
+            _ => false,
+        })
+        .map(Unmappable::from)
+        .collect::<Vec<_>>();
+
+    if escapes {
+        res.extend(
+            text.char_indices()
+                .enumerate()
+                .filter(|(_, (_, c))| *c == '/')
+                .map(unmappable),
+        );
+        res.sort_by_key(|v| v.byte_offset);
+    }
+
+    res
+}
+
+fn validate(text: &str, scheme: Scheme) -> Result<(), TransliterationError> {
+    let unmappable = match scheme {
+        // देवनागरी is read as is, so only its letters are checked
+        Scheme::Devanāgarī => text
+            .char_indices()
+            .enumerate()
+            .filter(|(_, (_, c))| !c.is_whitespace() && dn::token_kind(*c) == TokenKind::Other)
+            .map(unmappable)
+            .collect(),
+        _ => unknown(text, scheme.clone()),
+    };
+
+    if unmappable.is_empty() {
+        Ok(())
+    } else {
+        Err(TransliterationError { scheme, unmappable })
+    }
+}
+
+macro_rules! text {
+    ($(#[$doc:meta])* $name:ident, $scheme:expr) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+        #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+        #[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
+        pub struct $name(String);
+
+        impl $name {
+            /// The scheme of the text.
+            pub const SCHEME: Scheme = $scheme;
+
+            /// This function checks that every character of `text` is part of the scheme, like
+            /// [`try_transliterate`](crate::try_transliterate) does.
+            pub fn new(text: impl Into<String>) -> Result<Self, TransliterationError> {
+                let text = text.into();
+                validate(&text, Self::SCHEME)?;
+
+                Ok(Self(text))
+            }
+
+            /// The text.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// This function returns the text, giving up the guarantee.
+            pub fn into_string(self) -> String {
+                self.0
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = TransliterationError;
+
+            fn try_from(text: String) -> Result<Self, Self::Error> {
+                Self::new(text)
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = TransliterationError;
+
+            fn try_from(text: &str) -> Result<Self, Self::Error> {
+                Self::new(text)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = TransliterationError;
+
+            fn from_str(text: &str) -> Result<Self, Self::Err> {
+                Self::new(text)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(v: $name) -> Self {
+                v.0
+            }
+        }
+
+        impl Deref for $name {
+            type Target = str;
+
+            fn deref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+    };
+}
+
+// देवनागरी writes every letter the other schemes read. any other scheme may not have a letter
+// of its source, which fails as with `try_transliterate`.
+#[cfg(any(feature = "gu", feature = "iast"))]
+macro_rules! convert {
+    ($a:ident, $b:ident) => {
+        convert!($a => $b);
+        convert!($b => $a);
+    };
+    ($from:ident => Devanāgarī) => {
+        impl From<&$from> for Devanāgarī {
+            fn from(v: &$from) -> Self {
+                Self(transliterate(&v.0, $from::SCHEME, Devanāgarī::SCHEME))
+            }
+        }
+
+        impl From<$from> for Devanāgarī {
+            fn from(v: $from) -> Self {
+                Self::from(&v)
+            }
+        }
+    };
+    ($from:ident => $to:ident) => {
+        impl TryFrom<&$from> for $to {
+            type Error = TransliterationError;
+
+            fn try_from(v: &$from) -> Result<Self, Self::Error> {
+                try_transliterate(&v.0, $from::SCHEME, $to::SCHEME).map(Self)
+            }
+        }
+
+        impl TryFrom<$from> for $to {
+            type Error = TransliterationError;
+
+            fn try_from(v: $from) -> Result<Self, Self::Error> {
+                Self::try_from(&v)
+            }
+        }
+    };
+}
+
+text!(
+    /// देवनागरी text.
+    Devanāgarī,
+    Scheme::Devanāgarī
+);
+#[cfg(feature = "gu")]
+text!(
+    /// ગુજરાતી text.
+    Gujarātī,
+    Scheme::Gujarātī
+);
+#[cfg(feature = "iast")]
+text!(
+    /// IAST text, without UAST-IO escapes.
+    Iast,
+    Scheme::Iast
+);
+#[cfg(feature = "slp")]
+text!(
+    /// SLP1 text.
+    Slp1,
+    Scheme::Slp1
+);
+
+#[cfg(feature = "gu")]
+convert!(Devanāgarī, Gujarātī);
+#[cfg(feature = "iast")]
+convert!(Devanāgarī, Iast);
+#[cfg(feature = "slp")]
+convert!(Devanāgarī, Slp1);
+#[cfg(all(feature = "gu", feature = "iast"))]
+convert!(Gujarātī, Iast);
+#[cfg(all(feature = "gu", feature = "slp"))]
+convert!(Gujarātī, Slp1);
+#[cfg(feature = "slp")]
+convert!(Iast, Slp1);
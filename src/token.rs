@@ -0,0 +1,173 @@
+//! Splitting देवनागरी, IAST and UAST-IO into the parts of their syllables
+
+use crate::dn;
+#[cfg(feature = "uast")]
+use crate::{
+    Options, Scheme, Unknown, uast,
+    utils::{Context, Sink, split_line_and_convert},
+};
+#[cfg(feature = "uast")]
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// What a [`Token`] is in a syllable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// An independent vowel, like अ.
+    Vowel,
+    /// A consonant, like क. Without a vowel sign or a halanta after it, it carries the inherent
+    /// vowel.
+    Consonant,
+    /// A dependent vowel, like ा.
+    VowelSign,
+    /// ्
+    Halanta,
+    /// ं
+    Anusvāra,
+    /// ः
+    Visarga,
+    /// ँ
+    Candrabindu,
+    /// ऽ
+    Avagraha,
+    /// ।
+    Daṇḍa,
+    /// ॥
+    DoubleDaṇḍa,
+    /// A digit, like ०.
+    Numeral,
+    /// A Vedic accent, like ॑.
+    VedicAccent,
+    /// ॐ
+    Om,
+    /// ॰ and ॱ
+    Abbreviation,
+    /// Anything that is not part of the script.
+    Other,
+}
+
+/// A part of a syllable.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Token {
+    /// What the token is.
+    pub kind: TokenKind,
+    /// The देवनागरी character of the token. For [`TokenKind::Other`], the first character of the
+    /// input.
+    pub letter: char,
+    /// Byte range of the token in the input.
+    pub span: Range<usize>,
+}
+
+/// This function splits देवनागरी into tokens. Whitespace is left out.
+///
+/// ```
+/// use uast::{TokenKind, tokenize_devanāgarī};
+///
+/// assert_eq!(
+///     vec![
+///         TokenKind::Consonant,
+///         TokenKind::Halanta,
+///         TokenKind::Consonant,
+///         TokenKind::VowelSign,
+///         TokenKind::Daṇḍa,
+///     ],
+///     tokenize_devanāgarī("स्वा।")
+///         .iter()
+///         .map(|v| v.kind)
+///         .collect::<Vec<_>>()
+/// );
+/// ```
+pub fn tokenize_devanāgarī(dn: &str) -> Vec<Token> {
+    dn.char_indices()
+        .filter(|(_, c)| !c.is_whitespace())
+        .map(|(i, c)| Token {
+            kind: dn::token_kind(c),
+            letter: c,
+            span: i..i + c.len_utf8(),
+        })
+        .collect()
+}
+
+/// This function splits UAST-IO or IAST into the same tokens as [`tokenize_devanāgarī`] gives for
+/// its देवनागरी. Whitespace is left out.
+///
+/// The span of a consonant includes its inherent vowel, and the span of an aspirate or a
+/// diphthong covers both of its letters. A consonant without a vowel is followed by a halanta
+/// that has an empty span at the end of the consonant.
+///
+/// ```
+/// use uast::{Token, TokenKind, tokenize_uast};
+///
+/// assert_eq!(
+///     vec![
+///         Token { kind: TokenKind::Consonant, letter: 'भ', span: 0..3 },
+///         Token { kind: TokenKind::Consonant, letter: 'र', span: 3..4 },
+///         Token { kind: TokenKind::Halanta, letter: '्', span: 4..4 },
+///         Token { kind: TokenKind::Consonant, letter: 'ग', span: 4..5 },
+///         Token { kind: TokenKind::VowelSign, letter: 'ो', span: 5..6 },
+///     ],
+///     tokenize_uast("bhargo")
+/// );
+/// ```
+#[cfg(feature = "uast")]
+pub fn tokenize_uast(line: &str) -> Vec<Token> {
+    let opts = Options {
+        unknown: Unknown::PassThrough,
+        ..Default::default()
+    };
+    let mut ctx = Context::new(opts, Scheme::UastIo, Scheme::Devanāgarī);
+    ctx.align = true;
+    ctx.collect = true;
+
+    let mut res = Vec::new();
+    let (mut buf, mut normalized) = (String::new(), String::new());
+
+    split_line_and_convert(
+        |word, ctx, _| {
+            // NFC and NFD input give the same tokens, with spans of the input
+            let input = if ctx.normalize(word, &mut normalized) {
+                normalized.as_str()
+            } else {
+                word
+            };
+
+            let n = ctx.diagnostics.len();
+            buf.clear();
+            ctx.start_stage(input);
+            uast::convertor(input, ctx, &mut buf)?;
+
+            let offset = ctx.offset();
+            for (input, output) in ctx.spans() {
+                let input = ctx.original(input);
+                let span = offset + input.start..offset + input.end;
+                let letter = buf[output].chars().next().unwrap_or('/');
+
+                // what is not UAST-IO has been copied through as is
+                let kind = if ctx.diagnostics[n..]
+                    .iter()
+                    .any(|v| v.byte_offset == span.start)
+                {
+                    TokenKind::Other
+                } else {
+                    dn::token_kind(letter)
+                };
+
+                let span = match kind {
+                    TokenKind::Halanta => span.end..span.end,
+                    _ => span,
+                };
+                res.push(Token { kind, letter, span });
+            }
+
+            Ok(())
+        },
+        line,
+        &mut ctx,
+        // whitespace is not tokenized
+        &mut Sink,
+    )
+    .expect("tokens are not written anywhere");
+
+    res
+}
@@ -1,12 +1,26 @@
 //! This module implements the functionality of UAST-IO and IAST to देवनागरी
 
-use crate::utils::split_line_and_convert;
+#[cfg(feature = "uast")]
+use crate::{
+    Options, Scheme, TransliterationError, transliterate, transliterate_into, try_transliterate,
+};
+use crate::{
+    dn,
+    engine::{self, Char, Letter, Parse},
+    utils::{Context, binary_search_str, lowercase},
+};
+#[cfg(feature = "uast")]
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::{
+    fmt::{self, Write},
+    mem,
+};
 
 type T = (&'static str, char);
 
 struct ScriptSpecials {
     om: char,
-    halanta: char,
     saṃkṣipta: char,
 }
 
@@ -20,88 +34,73 @@ struct LangMap {
 }
 
 impl LangMap {
-    fn binary_search(arr: &[T], c: &[char]) -> Option<String> {
-        let mut i = 0_isize;
-        let mut j = (arr.len() - 1) as isize;
-        let txt = c.iter().collect::<String>();
-
-        while i <= j {
-            let m = (i + j) / 2;
-
-            let v = arr[m as usize];
-
-            if txt == v.0 {
-                return Some(v.1.to_string());
-            }
-
-            if txt > v.0.parse().unwrap() {
-                i = m + 1;
-            } else {
-                j = m - 1;
-            }
-        }
-
-        None
+    fn binary_search(arr: &[T], c: &[char]) -> Option<char> {
+        binary_search_str(arr, c)
     }
 
-    fn get_vowel(&self, c: &[char]) -> Option<String> {
+    fn get_vowel(&self, c: &[char]) -> Option<char> {
         Self::binary_search(&self.vowels, c)
     }
 
-    fn get_vowelsign(&self, c: &[char]) -> Option<String> {
+    fn get_vowelsign(&self, c: &[char]) -> Option<char> {
         Self::binary_search(&self.vowel_signs, c)
     }
 
-    fn get_number(&self, c: &[char]) -> Option<String> {
+    fn get_number(&self, c: &[char]) -> Option<char> {
         Self::binary_search(&self.numbers, c)
     }
 
-    fn get_misc(&self, c: &[char]) -> Option<String> {
+    fn get_misc(&self, c: &[char]) -> Option<char> {
         Self::binary_search(&self.misc, c)
     }
 
-    fn get_consonant(&self, c: &[char]) -> Option<String> {
+    fn get_consonant(&self, c: &[char]) -> Option<char> {
         Self::binary_search(&self.consonants, c)
     }
 
-    fn contains(arr: &[T], c: &str) -> bool {
-        let mut i = 0_isize;
-        let mut j = (arr.len() - 1) as isize;
-
-        while i <= j {
-            let m = (i + j) / 2;
-
-            let v = arr[m as usize];
-
-            if c == v.0 {
-                return true;
-            }
-
-            if c > v.0 {
-                i = m + 1;
-            } else {
-                j = m - 1;
+    // ॐ, ॰ and the Vedic accents are read as they are
+    fn get_special(&self, c: &[char]) -> Option<char> {
+        match *c {
+            [c] if c == self.specials.om
+                || c == self.specials.saṃkṣipta
+                || dn::is_veda_accent(c) =>
+            {
+                Some(c)
             }
+            _ => None,
         }
-
-        false
-    }
-
-    fn contains_vowel(&self, c: &str) -> bool {
-        Self::contains(&self.vowels, c)
     }
+}
 
-    fn contains_vowelsign(&self, c: &str) -> bool {
-        Self::contains(&self.vowel_signs, c)
+impl Parse for LangMap {
+    fn longest_match(&self, data: &[Char]) -> Option<(usize, Letter)> {
+        // aspirates and diphthongs are the only letters of two characters
+        (1..=data.len().min(2)).rev().find_map(|n| {
+            let mut key = ['\0'; 2];
+            for (k, v) in key.iter_mut().zip(data) {
+                *k = v.c;
+            }
+            let key = &key[..n];
+
+            let l = Letter {
+                vowel: self.get_vowel(key),
+                sign: self.get_vowelsign(key),
+                consonant: self.get_consonant(key),
+                other: self
+                    .get_misc(key)
+                    .or_else(|| self.get_number(key))
+                    .or_else(|| self.get_special(key)),
+            };
+            (l != Letter::default()).then_some((n, l))
+        })
     }
 
-    fn contains_consonant(&self, c: &str) -> bool {
-        Self::contains(&self.consonants, c)
+    // unknown escapes have already been recorded by `handle_escape`
+    fn recorded(&self, c: &Char) -> bool {
+        c.c == '/'
     }
 }
 
-static VEDA_ACCENTS: [char; 14] = ['॑', '॒', '᳚', '꣠', '꣡', '꣢', '꣣', '꣤', '꣥', '꣦', '꣧', '꣨', '꣩', 'ꣳ'];
-
 static UNICODE_MAP: [T; 34] = [
     ("'", '॑'),
     ("''", '᳚'),
@@ -140,26 +139,10 @@ static UNICODE_MAP: [T; 34] = [
 ];
 
 fn unicode_map_binary_search(c: &str) -> Option<char> {
-    let mut i = 0_isize;
-    let mut j = (UNICODE_MAP.len() - 1) as isize;
-
-    while i <= j {
-        let m = (i + j) / 2;
-
-        let v = UNICODE_MAP[m as usize];
-
-        if c == v.0 {
-            return Some(v.1);
-        }
-
-        if c > v.0 {
-            i = m + 1;
-        } else {
-            j = m - 1;
-        }
-    }
-
-    None
+    UNICODE_MAP
+        .binary_search_by(|v| v.0.chars().cmp(c.chars().map(lowercase)))
+        .ok()
+        .map(|i| UNICODE_MAP[i].1)
 }
 
 static CHAR_DICT: LangMap = LangMap {
@@ -253,192 +236,69 @@ static CHAR_DICT: LangMap = LangMap {
     ],
     specials: ScriptSpecials {
         om: 'ॐ',
-        halanta: '्',
         saṃkṣipta: '॰',
     },
 };
 
-static UNASPIRATED_CONSONANTS: [char; 10] = ['b', 'c', 'd', 'g', 'j', 'k', 'p', 't', 'ḍ', 'ṭ'];
-
-fn char_slice_contains(slice: &[char], c: char) -> bool {
-    let mut i = 0_isize;
-    let mut j = (slice.len() - 1) as isize;
-
-    while i <= j {
-        let m = (i + j) / 2;
-
-        let v = slice[m as usize];
-
-        if c == v {
-            return true;
-        }
-
-        if c > v {
-            i = m + 1;
-        } else {
-            j = m - 1;
-        }
-    }
-
-    false
+/// Every UAST-IO escape without its slashes, along with the character it is read as
+#[cfg(feature = "uast")]
+pub(crate) fn escapes() -> &'static [(&'static str, char)] {
+    &UNICODE_MAP
 }
 
-fn handle_unicode(uast: &str) -> Vec<char> {
-    let str = uast.to_lowercase().chars().collect::<Vec<char>>();
-
-    let mut arr = Vec::<char>::with_capacity(str.len());
+/// Whether `k` is a UAST-IO escape without its slashes
+#[cfg(feature = "uast")]
+pub(crate) fn is_escape(k: &str) -> bool {
+    unicode_map_binary_search(k).is_some()
+}
 
+fn handle_unicode(uast: &str, arr: &mut Vec<Char>, ctx: &mut Context) {
     let mut i = 0;
-    while i < str.len() {
-        let curr = str[i];
-
-        if curr != '/' {
-            arr.push(curr);
-            i += 1;
+    while let Some(curr) = uast[i..].chars().next() {
+        if curr == '/' {
+            i = handle_escape(uast, i, arr, ctx);
             continue;
         }
 
-        let mut c = String::with_capacity(2);
-
-        for j in (i + 1)..str.len() {
-            let curr = str[j];
-            if curr == '/' {
-                i = j;
-                break;
-            }
-
-            if j == str.len() - 1 {
-                i = j;
-            }
-
-            c.push(curr);
-        }
-
-        if let Some(v) = unicode_map_binary_search(&c) {
-            arr.push(v);
-        }
-
-        i += 1;
+        arr.push(Char {
+            c: lowercase(curr),
+            start: i,
+            end: i + curr.len_utf8(),
+        });
+        i += curr.len_utf8();
     }
-
-    arr
 }
 
-fn iast_to_devanāgarī(data: Vec<char>) -> String {
-    if data.is_empty() {
-        return "".to_string();
-    }
-
-    let mut arr = Vec::<String>::with_capacity(data.len());
-    let mut i = 0;
-
-    // here's a little thing about how saṃskṛta and devanāgarī work:
-    // the general formation of a syllable in saṃskṛta is `consonant + vowel_sign`.
-    // there is a designated symbol for each consonant and vowel_sign in devanāgarī.
-    // in the case where the word may start with a vowel, we put a special symbol instead of its sign.
-    // if we encounter a miscellaneous symbol, we put its designated symbol and start finding the consonant again.
-    // in the case that the `misc` symbol occurs in middle of a word like an avagraha, we first have to finish the
-    // preceding syllable. if that syllable was a running consonant, we add a halanta. we also add halanta
-    // at end of word to represent a consonant without a corresponding vowel with it.
-
-    // starts with vowel
-    if LangMap::contains_vowel(&CHAR_DICT, data[0].to_string().as_str()) {
-        if i + 1 < data.len() && data[0] == 'a' && (data[1] == 'i' || data[1] == 'u') {
-            i = 2;
-        } else {
-            i = 1;
+// an escape runs till the next `/` or the end of word. returns where it ends.
+fn handle_escape(uast: &str, start: usize, arr: &mut Vec<Char>, ctx: &mut Context) -> usize {
+    let (c, end) = match uast[start + 1..].find('/') {
+        Some(j) => (&uast[start + 1..start + 1 + j], start + j + 2),
+        None => (&uast[start + 1..], uast.len()),
+    };
+
+    if let Some(v) = unicode_map_binary_search(c) {
+        arr.push(Char { c: v, start, end });
+    } else {
+        ctx.unknown_escape(uast, start..end);
+
+        // `/` is not a legal character, so the whole escape is copied through as is
+        if ctx.pass_through() {
+            arr.push(Char { c: '/', start, end });
         }
-
-        // a valid vowel exists here
-        arr.push(CHAR_DICT.get_vowel(&data[0..i]).unwrap());
     }
 
-    while i < data.len() {
-        if char_slice_contains(&VEDA_ACCENTS, data[i]) {
-            arr.push(data[i].to_string());
-            i += 1;
-            continue;
-        }
-
-        if data[i] == CHAR_DICT.specials.om {
-            arr.push(CHAR_DICT.specials.om.to_string());
-            i += 1;
-            continue;
-        }
-
-        if data[i] == CHAR_DICT.specials.saṃkṣipta {
-            arr.push(CHAR_DICT.specials.saṃkṣipta.to_string());
-            i += 1;
-            continue;
-        }
-
-        let c = data[i].to_string();
-        if let Some(v) = CHAR_DICT.get_misc(&data[i..i + 1]) {
-            if i + 1 < data.len() && data[i] == '.' && data[i + 1] == '.' {
-                arr.push('॥'.to_string());
-                i += 2;
-            } else {
-                arr.push(v.to_string());
-                i += 1;
-            }
-            continue;
-        }
-
-        if let Some(v) = CHAR_DICT.get_number(&data[i..i + 1]) {
-            arr.push(v.to_string());
-            i += 1;
-            continue;
-        }
-
-        // at this point, if we find any illegal character then we simply ignore it
-        if !LangMap::contains_vowelsign(&CHAR_DICT, c.as_str())
-            && !LangMap::contains_vowel(&CHAR_DICT, c.as_str())
-            && !LangMap::contains_consonant(&CHAR_DICT, c.as_str())
-        {
-            i += 1;
-            continue;
-        }
-
-        if i + 1 < data.len()
-            && char_slice_contains(&UNASPIRATED_CONSONANTS, data[i])
-            && data[i + 1] == 'h'
-        {
-            // a valid aspirated consonant exists here
-            arr.push(CHAR_DICT.get_consonant(&data[i..i + 2]).unwrap());
-            i += 2;
-        } else {
-            // if valid consonant then push it else ignore invalid consonants completely
-            if let Some(v) = CHAR_DICT.get_consonant(&data[i..i + 1]) {
-                arr.push(v.to_string());
-                i += 1;
-            }
-        }
+    end
+}
 
-        // if end of word or anything other than a vowel-sign then we just push a halanta and start the process again
-        if i == data.len()
-            || (!LangMap::contains_vowelsign(&CHAR_DICT, data[i].to_string().as_str())
-                && data[i] != 'a')
-        {
-            arr.push(CHAR_DICT.specials.halanta.to_string());
-            continue;
-        }
+pub(crate) fn convertor(line: &str, ctx: &mut Context, out: &mut dyn Write) -> fmt::Result {
+    let mut chars = mem::take(&mut ctx.chars);
+    chars.clear();
 
-        if i + 1 < data.len() && data[i] == 'a' && (data[i + 1] == 'i' || data[i + 1] == 'u') {
-            arr.push(CHAR_DICT.get_vowelsign(&data[i..i + 2]).unwrap());
-            i += 2;
-        } else {
-            if data[i] != 'a' {
-                arr.push(CHAR_DICT.get_vowelsign(&data[i..i + 1]).unwrap());
-            }
-            i += 1;
-        }
-    }
-
-    arr.join("")
-}
+    handle_unicode(line, &mut chars, ctx);
+    let res = engine::read_roman(line, &chars, &CHAR_DICT, ctx, out);
 
-fn convertor(line: &str) -> String {
-    iast_to_devanāgarī(handle_unicode(line))
+    ctx.chars = chars;
+    res
 }
 
 /// This function can accept both UAST-IO and IAST and returns देवनागरी.
@@ -453,6 +313,52 @@ fn convertor(line: &str) -> String {
 ///     uast_to_devanāgarī(&s)
 /// );
 /// ```
+#[cfg(feature = "uast")]
 pub fn uast_to_devanāgarī(line: &str) -> String {
-    split_line_and_convert(convertor, line)
+    transliterate(line, Scheme::UastIo, Scheme::Devanāgarī)
+}
+
+/// This function can accept both UAST-IO and IAST and returns देवनागरी. It fails on any illegal
+/// character or unknown `/…/` escape.
+///
+/// ```
+/// use uast::try_uast_to_devanāgarī;
+///
+/// assert_eq!("भर्गो", try_uast_to_devanāgarī("bhargo").unwrap());
+///
+/// let err = try_uast_to_devanāgarī("bhargo /xy/ bhargo!").unwrap_err();
+/// assert_eq!(
+///     vec![("/xy/", 7), ("!", 18)],
+///     err.unmappable
+///         .iter()
+///         .map(|v| (v.text.as_str(), v.byte_offset))
+///         .collect::<Vec<_>>()
+/// );
+/// ```
+#[cfg(feature = "uast")]
+pub fn try_uast_to_devanāgarī(line: &str) -> Result<String, TransliterationError> {
+    try_transliterate(line, Scheme::UastIo, Scheme::Devanāgarī)
+}
+
+/// This function is [`uast_to_devanāgarī`] that appends to `out` instead of allocating.
+///
+/// ```
+/// use uast::uast_to_devanāgarī_into;
+///
+/// let mut s = String::from("॥ ");
+/// uast_to_devanāgarī_into("/om/ namaḥ", &mut s).unwrap();
+/// assert_eq!("॥ ॐ नमः", s);
+/// ```
+#[cfg(feature = "uast")]
+pub fn uast_to_devanāgarī_into<W>(line: &str, out: &mut W) -> fmt::Result
+where
+    W: Write + ?Sized,
+{
+    transliterate_into(
+        line,
+        Scheme::UastIo,
+        Scheme::Devanāgarī,
+        &Options::default(),
+        out,
+    )
 }
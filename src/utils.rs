@@ -1,20 +1,491 @@
 //! Utility function for splitting line and converting
 
-pub(crate) fn split_line_and_convert<F>(f: F, s: &str) -> String
+use crate::{
+    Alignment, Diagnostic, DiagnosticKind, Normalization, Options, Scheme, Unknown,
+    engine::{Char, SchemeIndex},
+    normalize,
+};
+use alloc::{string::String, vec::Vec};
+use core::{
+    fmt::{self, Write},
+    mem,
+    ops::Range,
+};
+
+// a diagnostic as it is recorded. what the first converter finds is kept as its length in the
+// input, and what a later converter finds is a single देवनागरी character.
+pub(crate) struct Record {
+    pub(crate) kind: DiagnosticKind,
+    pub(crate) byte_offset: usize,
+    char_offset: usize,
+    text: Result<usize, char>,
+}
+
+// bytes `input` of the input of a converter written as bytes `output` and characters `chars` of
+// its output
+#[derive(Clone)]
+pub(crate) struct Span {
+    input: Range<usize>,
+    output: Range<usize>,
+    chars: Range<usize>,
+}
+
+/// State shared by the converters over one conversion
+pub(crate) struct Context {
+    pub(crate) opts: Options,
+    pub(crate) from: Scheme,
+    pub(crate) to: Scheme,
+    // a conversion may chain several converters over each word. only the first one reads the
+    // input, so only it can tell where exactly in the word a character was found.
+    pub(crate) first_stage: bool,
+    pub(crate) last_stage: bool,
+    // byte and character offsets of the word being converted
+    byte_offset: usize,
+    char_offset: usize,
+    // diagnostics are only recorded when they are asked for
+    pub(crate) collect: bool,
+    pub(crate) diagnostics: Vec<Record>,
+    // scratch space reused for every word
+    pub(crate) chars: Vec<Char>,
+    pub(crate) stages: [String; 2],
+    // the word once normalized, a copy of the word and the bytes of both of every letter along
+    // with its marks. `segments` is empty when the word was normalized already.
+    pub(crate) normalized: String,
+    word: String,
+    segments: Vec<(Range<usize>, Range<usize>)>,
+    // letters of the custom schemes that do not keep an index of their own
+    pub(crate) source: Option<SchemeIndex>,
+    pub(crate) target: Option<SchemeIndex>,
+    // `alignment` is only filled in when `align` is set. the spans of the earlier stages are kept
+    // in `prev_spans` mapped back to the word.
+    pub(crate) align: bool,
+    pub(crate) alignment: Vec<Alignment>,
+    spans: Vec<Span>,
+    prev_spans: Vec<Span>,
+    word_len: usize,
+    stage_len: usize,
+    stage_chars: usize,
+    out_chars: usize,
+    // bytes of the output of the current stage and of its input that were copied through as is,
+    // which later stages leave alone. `piece` is where in its input the current stage was started
+    // again after such bytes.
+    opaque: Vec<Range<usize>>,
+    pub(crate) prev_opaque: Vec<Range<usize>>,
+    pub(crate) piece: usize,
+}
+
+impl Context {
+    pub(crate) fn new(opts: Options, from: Scheme, to: Scheme) -> Self {
+        let (source, target) = (index(&from), index(&to));
+
+        Self {
+            opts,
+            from,
+            to,
+            first_stage: true,
+            last_stage: true,
+            byte_offset: 0,
+            char_offset: 0,
+            collect: false,
+            diagnostics: Vec::new(),
+            chars: Vec::new(),
+            stages: [String::new(), String::new()],
+            normalized: String::new(),
+            word: String::new(),
+            segments: Vec::new(),
+            source,
+            target,
+            align: false,
+            alignment: Vec::new(),
+            spans: Vec::new(),
+            prev_spans: Vec::new(),
+            word_len: 0,
+            stage_len: 0,
+            stage_chars: 0,
+            out_chars: 0,
+            opaque: Vec::new(),
+            prev_opaque: Vec::new(),
+            piece: 0,
+        }
+    }
+
+    pub(crate) fn pass_through(&self) -> bool {
+        self.opts.unknown == Unknown::PassThrough
+    }
+
+    fn push(&mut self, kind: DiagnosticKind, word: &str, range: Range<usize>) {
+        if !self.collect {
+            return;
+        }
+
+        let record = if self.first_stage {
+            // the first converter reads the normalized word
+            let (word, range) = if self.segments.is_empty() {
+                (word, range)
+            } else {
+                (self.word.as_str(), self.original(range))
+            };
+
+            Record {
+                kind,
+                byte_offset: self.byte_offset + range.start,
+                char_offset: self.char_offset + word[..range.start].chars().count(),
+                text: Ok(range.len()),
+            }
+        } else {
+            let c = word[range].chars().next().expect("a character is recorded");
+
+            Record {
+                kind,
+                byte_offset: self.byte_offset,
+                char_offset: self.char_offset,
+                text: Err(c),
+            }
+        };
+        self.diagnostics.push(record);
+    }
+
+    /// The recorded diagnostics of a conversion of `input`
+    pub(crate) fn take_diagnostics(&mut self, input: &str) -> Vec<Diagnostic> {
+        mem::take(&mut self.diagnostics)
+            .into_iter()
+            .map(|v| Diagnostic {
+                kind: v.kind,
+                byte_offset: v.byte_offset,
+                char_offset: v.char_offset,
+                text: match v.text {
+                    Ok(n) => input[v.byte_offset..v.byte_offset + n].into(),
+                    Err(c) => c.into(),
+                },
+            })
+            .collect()
+    }
+
+    /// Records the bytes `range` of `word` that the converter has no mapping for
+    pub(crate) fn unknown(&mut self, word: &str, range: Range<usize>) {
+        if !self.collect {
+            return;
+        }
+
+        // a देवनागरी character is either valid input that the target cannot write, or it was
+        // produced by an earlier converter. anything else reaching a later converter was already
+        // recorded by the first one.
+        let devanāgarī = word[range.clone()]
+            .chars()
+            .next()
+            .is_some_and(is_devanāgarī);
+
+        if devanāgarī && (self.from == Scheme::Devanāgarī || !self.first_stage) {
+            self.push(
+                DiagnosticKind::Unrepresentable(self.to.clone()),
+                word,
+                range,
+            );
+        } else if self.first_stage {
+            self.push(DiagnosticKind::Unknown(self.from.clone()), word, range);
+        }
+    }
+
+    /// Records the bytes `range` of `word` as a `/…/` sequence that is not a UAST-IO escape
+    #[cfg(feature = "iast")]
+    pub(crate) fn unknown_escape(&mut self, word: &str, range: Range<usize>) {
+        self.push(DiagnosticKind::UnknownEscape, word, range);
+    }
+
+    /// Records the bytes `range` of `word` that are copied as is to the output
+    pub(crate) fn copied(&mut self, word: &str, range: Range<usize>) {
+        if self.collect && self.last_stage {
+            self.push(DiagnosticKind::CopiedAsIs(self.to.clone()), word, range);
+        }
+    }
+
+    /// Writes `s` to `out` as the output of the bytes `range` of the word
+    pub(crate) fn write_str(
+        &mut self,
+        out: &mut dyn Write,
+        range: Range<usize>,
+        s: &str,
+    ) -> fmt::Result {
+        let (len, chars) = if self.last_stage && self.opts.normalization == Normalization::Nfd {
+            normalize::write_nfd(s, out)?
+        } else {
+            out.write_str(s)?;
+            (s.len(), if self.align { s.chars().count() } else { 0 })
+        };
+
+        let start = self.stage_len;
+        self.stage_len += len;
+
+        if self.align {
+            let chars = self.stage_chars + chars;
+
+            self.spans.push(Span {
+                input: range.start + self.piece..range.end + self.piece,
+                output: start..self.stage_len,
+                chars: self.stage_chars..chars,
+            });
+            self.stage_chars = chars;
+        }
+
+        Ok(())
+    }
+
+    /// Copies `s`, the bytes `range` of the word that the converter does not recognise, to `out`
+    /// as is when unknown characters are passed through
+    pub(crate) fn pass(
+        &mut self,
+        out: &mut dyn Write,
+        range: Range<usize>,
+        s: &str,
+    ) -> fmt::Result {
+        if self.pass_through() {
+            self.copy_through(out, range, s)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes `s` to `out` as the output of the bytes `range` of the word, and keeps the converters
+    /// after this one from reading it
+    pub(crate) fn copy_through(
+        &mut self,
+        out: &mut dyn Write,
+        range: Range<usize>,
+        s: &str,
+    ) -> fmt::Result {
+        let start = self.stage_len;
+        self.write_str(out, range, s)?;
+
+        if !self.last_stage {
+            match self.opaque.last_mut() {
+                Some(v) if v.end == start => v.end = self.stage_len,
+                _ => self.opaque.push(start..self.stage_len),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes `c` to `out` as the output of the bytes `range` of the word
+    pub(crate) fn write_char(
+        &mut self,
+        out: &mut dyn Write,
+        range: Range<usize>,
+        c: char,
+    ) -> fmt::Result {
+        self.write_str(out, range, c.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Makes the last output also come from the bytes of the word up to `end`, like the inherent
+    /// vowel of a consonant that has no letter of its own
+    pub(crate) fn extend(&mut self, end: usize) {
+        if let Some(v) = self.spans.last_mut() {
+            v.input.end = v.input.end.max(self.piece + end);
+        }
+    }
+
+    /// Byte offset of the word being converted
+    #[cfg(feature = "uast")]
+    pub(crate) fn offset(&self) -> usize {
+        self.byte_offset
+    }
+
+    /// Bytes of the word and of the output of every piece the current converter has written
+    #[cfg(feature = "uast")]
+    pub(crate) fn spans(&self) -> impl Iterator<Item = (Range<usize>, Range<usize>)> + '_ {
+        self.spans
+            .iter()
+            .map(|v| (v.input.clone(), v.output.clone()))
+    }
+
+    /// Writes `word` in NFC to `out`. Returns false when it is in NFC already, and then the word
+    /// is to be read as is.
+    pub(crate) fn normalize(&mut self, word: &str, out: &mut String) -> bool {
+        self.segments.clear();
+        if !normalize::nfc(word, out, &mut self.segments) {
+            return false;
+        }
+
+        self.word.clear();
+        self.word.push_str(word);
+        true
+    }
+
+    /// Bytes of the word that the bytes `range` of the normalized word were read from
+    pub(crate) fn original(&self, range: Range<usize>) -> Range<usize> {
+        if self.segments.is_empty() {
+            return range;
+        }
+
+        let find = |i: usize| self.segments.partition_point(|v| v.0.end <= i);
+        let start = self
+            .segments
+            .get(find(range.start))
+            .map_or(self.word.len(), |v| v.1.start);
+        let end = match range.end.checked_sub(1) {
+            Some(i) if range.start < range.end => self.segments[find(i)].1.end,
+            _ => start,
+        };
+
+        start..end
+    }
+
+    pub(crate) fn start_stage(&mut self, word: &str) {
+        self.stage_len = 0;
+        mem::swap(&mut self.opaque, &mut self.prev_opaque);
+        self.opaque.clear();
+        if self.first_stage {
+            self.prev_opaque.clear();
+        }
+
+        if !self.align {
+            return;
+        }
+
+        if self.first_stage {
+            self.word_len = word.len();
+            self.prev_spans.clear();
+        } else {
+            mem::swap(&mut self.spans, &mut self.prev_spans);
+        }
+
+        self.spans.clear();
+        self.stage_chars = 0;
+    }
+
+    pub(crate) fn end_stage(&mut self) {
+        if !self.align || self.first_stage {
+            return;
+        }
+
+        // the input of this stage is the output of the previous one, whose spans are already
+        // relative to the word
+        for v in &mut self.spans {
+            let (start, end) = self
+                .prev_spans
+                .iter()
+                .filter(|p| {
+                    (p.output.start < v.input.end && v.input.start < p.output.end)
+                        || p.output.start == v.input.start
+                })
+                .fold((usize::MAX, 0), |(s, e), p| {
+                    (s.min(p.input.start), e.max(p.input.end))
+                });
+
+            v.input = if start < end {
+                start..end
+            } else {
+                0..self.word_len
+            };
+        }
+    }
+
+    /// Adds the spans of the word, whose output is complete, to `alignment`
+    pub(crate) fn end_word(&mut self, word: &str) {
+        if !self.align {
+            return;
+        }
+
+        for v in &self.spans {
+            let input = self.original(v.input.clone());
+            let start = self.char_offset + word[..input.start].chars().count();
+            let source = start..start + word[input].chars().count();
+            let output = self.out_chars + v.chars.start..self.out_chars + v.chars.end;
+
+            // a consonant and the halanta inserted after it come from the same letters
+            match self.alignment.last_mut() {
+                Some(p) if p.source == source && p.output.end == output.start => {
+                    p.output.end = output.end;
+                }
+                _ => self.alignment.push(Alignment { source, output }),
+            }
+        }
+
+        self.out_chars += self.stage_chars;
+    }
+
+    // whitespace is copied as is
+    fn whitespace(&mut self, chars: usize) {
+        if self.align && chars > 0 {
+            self.alignment.push(Alignment {
+                source: self.char_offset..self.char_offset + chars,
+                output: self.out_chars..self.out_chars + chars,
+            });
+            self.out_chars += chars;
+        }
+    }
+}
+
+// a custom scheme that does not keep an index of its own is indexed once per `Context`
+fn index(scheme: &Scheme) -> Option<SchemeIndex> {
+    match scheme {
+        Scheme::Custom(v) if v.index().is_none() => {
+            Some(SchemeIndex::new(v.kind(), v.entries(), v.escapes()))
+        }
+        _ => None,
+    }
+}
+
+/// Output that is thrown away
+pub(crate) struct Sink;
+
+impl Write for Sink {
+    fn write_str(&mut self, _: &str) -> fmt::Result {
+        Ok(())
+    }
+}
+
+// देवनागरी along with the Vedic Extensions and देवनागरी Extended blocks
+pub(crate) fn is_devanāgarī(c: char) -> bool {
+    matches!(c, '\u{0900}'..='\u{097F}' | '\u{1CD0}'..='\u{1CFF}' | '\u{A8E0}'..='\u{A8FF}')
+}
+
+pub(crate) fn is_gujarātī(c: char) -> bool {
+    matches!(c, '\u{0A80}'..='\u{0AFF}')
+}
+
+pub(crate) fn lowercase(c: char) -> char {
+    let mut l = c.to_lowercase();
+
+    match (l.next(), l.next()) {
+        (Some(v), None) => v,
+        _ => c,
+    }
+}
+
+pub(crate) fn split_line_and_convert<F>(
+    mut f: F,
+    s: &str,
+    ctx: &mut Context,
+    out: &mut dyn Write,
+) -> fmt::Result
 where
-    F: Fn(&str) -> String,
+    F: FnMut(&str, &mut Context, &mut dyn Write) -> fmt::Result,
 {
-    let mut res = String::new();
-    let mut first = true;
+    let mut rest = s;
+
+    // whitespace is copied as is so that indentation, tabs and newlines survive the conversion
+    while !rest.is_empty() {
+        let n = rest
+            .find(|c: char| !c.is_whitespace())
+            .unwrap_or(rest.len());
+        out.write_str(&rest[..n])?;
+        let chars = rest[..n].chars().count();
+        ctx.whitespace(chars);
+        ctx.byte_offset += n;
+        ctx.char_offset += chars;
+        rest = &rest[n..];
 
-    for word in s.split_whitespace() {
-        if !first {
-            res.push(' ');
+        let n = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        if n > 0 {
+            f(&rest[..n], ctx, out)?;
         }
-        res.push_str(&f(word));
-        first = false;
+        ctx.byte_offset += n;
+        ctx.char_offset += rest[..n].chars().count();
+        rest = &rest[n..];
     }
-    res
+
+    Ok(())
 }
 
 pub(crate) fn binary_search<U, V, F>(arr: &[(char, U)], c: char, f: F) -> Option<V>
@@ -39,17 +510,21 @@ where
         }
 
         if c > v.0 {
-            i = match m.checked_add(1) {
-                Some(val) => val,
-                None => return None,
-            };
+            i = m.checked_add(1)?;
         } else {
-            j = match m.checked_sub(1) {
-                Some(val) => val,
-                None => return None,
-            };
+            j = m.checked_sub(1)?;
         }
     }
 
     None
 }
+
+#[cfg(feature = "iast")]
+pub(crate) fn binary_search_str<V>(arr: &[(&str, V)], c: &[char]) -> Option<V>
+where
+    V: Copy,
+{
+    arr.binary_search_by(|v| v.0.chars().cmp(c.iter().copied()))
+        .ok()
+        .map(|i| arr[i].1)
+}
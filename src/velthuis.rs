@@ -0,0 +1,219 @@
+//! Velthuis to देवनागरी and देवनागरी to Velthuis
+
+use crate::{
+    Options, Scheme, TransliterationError,
+    roman::{Letters, Roman},
+    transliterate, transliterate_into, try_transliterate,
+    utils::Context,
+};
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+const LETTERS: Letters = Letters {
+    vowels: &[
+        ('अ', &["a"]),
+        ('आ', &["aa"]),
+        ('इ', &["i"]),
+        ('ई', &["ii"]),
+        ('उ', &["u"]),
+        ('ऊ', &["uu"]),
+        ('ऋ', &[".r"]),
+        ('ऌ', &[".l"]),
+        ('ए', &["e"]),
+        ('ऐ', &["ai"]),
+        ('ओ', &["o"]),
+        ('औ', &["au"]),
+        ('ॠ', &[".rr"]),
+        ('ॡ', &[".ll"]),
+    ],
+    vowel_signs: &[
+        ('ा', &["aa"]),
+        ('ि', &["i"]),
+        ('ी', &["ii"]),
+        ('ु', &["u"]),
+        ('ू', &["uu"]),
+        ('ृ', &[".r"]),
+        ('ॄ', &[".rr"]),
+        ('े', &["e"]),
+        ('ै', &["ai"]),
+        ('ो', &["o"]),
+        ('ौ', &["au"]),
+        ('ॢ', &[".l"]),
+        ('ॣ', &[".ll"]),
+    ],
+    consonants: &[
+        ('क', &["k"]),
+        ('ख', &["kh"]),
+        ('ग', &["g"]),
+        ('घ', &["gh"]),
+        ('ङ', &["\"n"]),
+        ('च', &["c"]),
+        ('छ', &["ch"]),
+        ('ज', &["j"]),
+        ('झ', &["jh"]),
+        ('ञ', &["~n"]),
+        ('ट', &[".t"]),
+        ('ठ', &[".th"]),
+        ('ड', &[".d"]),
+        ('ढ', &[".dh"]),
+        ('ण', &[".n"]),
+        ('त', &["t"]),
+        ('थ', &["th"]),
+        ('द', &["d"]),
+        ('ध', &["dh"]),
+        ('न', &["n"]),
+        ('प', &["p"]),
+        ('फ', &["ph"]),
+        ('ब', &["b"]),
+        ('भ', &["bh"]),
+        ('म', &["m"]),
+        ('य', &["y"]),
+        ('र', &["r"]),
+        ('ल', &["l"]),
+        ('व', &["v"]),
+        ('श', &["\"s"]),
+        ('ष', &[".s"]),
+        ('स', &["s"]),
+        ('ह', &["h"]),
+    ],
+    misc: &[
+        ('ँ', &["/"]),
+        ('ं', &[".m"]),
+        ('ः', &[".h"]),
+        ('ऽ', &[".a"]),
+        ('्', &["&"]),
+        ('।', &["."]),
+        ('॥', &[".."]),
+        ('०', &["0"]),
+        ('१', &["1"]),
+        ('२', &["2"]),
+        ('३', &["3"]),
+        ('४', &["4"]),
+        ('५', &["5"]),
+        ('६', &["6"]),
+        ('७', &["7"]),
+        ('८', &["8"]),
+        ('९', &["9"]),
+    ],
+};
+
+pub(crate) static SCHEME: Roman = Roman {
+    letters: LETTERS,
+    readings: &LETTERS.readings::<{ LETTERS.count() }>(),
+    longest: LETTERS.longest(),
+    inherent: "a",
+    copies: &['ॐ', '॰'],
+    separator: None,
+    escapes: &[],
+    lowercase: false,
+};
+
+pub(crate) fn velthuis_convertor(
+    velthuis: &str,
+    ctx: &mut Context,
+    out: &mut dyn Write,
+) -> fmt::Result {
+    SCHEME.read(velthuis, ctx, out)
+}
+
+pub(crate) fn convertor(dn: &str, ctx: &mut Context, out: &mut dyn Write) -> fmt::Result {
+    SCHEME.write(dn, ctx, out)
+}
+
+/// This function converts Velthuis to देवनागरी.
+///
+/// ```
+/// use uast::velthuis_to_devanāgarī;
+///
+/// let s = "bhuurbhuva.h sva.h tatsaviturvare.nya.m bhargo devasya dhiimahi . dhiyo yo na.h pracodayaat ..";
+/// assert_eq!(
+///     "भूर्भुवः स्वः तत्सवितुर्वरेण्यं भर्गो देवस्य धीमहि । धियो यो नः प्रचोदयात् ॥",
+///     velthuis_to_devanāgarī(&s)
+/// );
+/// ```
+pub fn velthuis_to_devanāgarī(velthuis: &str) -> String {
+    transliterate(velthuis, Scheme::Velthuis, Scheme::Devanāgarī)
+}
+
+/// This function converts Velthuis to देवनागरी and fails on any character that is not Velthuis.
+///
+/// ```
+/// use uast::try_velthuis_to_devanāgarī;
+///
+/// assert_eq!("कॢप्तः", try_velthuis_to_devanāgarī("k.lpta.h").unwrap());
+/// assert_eq!("ā", try_velthuis_to_devanāgarī("raama rāma").unwrap_err().unmappable[0].text);
+/// ```
+pub fn try_velthuis_to_devanāgarī(velthuis: &str) -> Result<String, TransliterationError> {
+    try_transliterate(velthuis, Scheme::Velthuis, Scheme::Devanāgarī)
+}
+
+/// This function is [`velthuis_to_devanāgarī`] that appends to `out` instead of allocating.
+///
+/// ```
+/// use uast::velthuis_to_devanāgarī_into;
+///
+/// let mut s = String::from("१. ");
+/// velthuis_to_devanāgarī_into("\"siva.h", &mut s).unwrap();
+/// assert_eq!("१. शिवः", s);
+/// ```
+pub fn velthuis_to_devanāgarī_into<W>(velthuis: &str, out: &mut W) -> fmt::Result
+where
+    W: Write + ?Sized,
+{
+    transliterate_into(
+        velthuis,
+        Scheme::Velthuis,
+        Scheme::Devanāgarī,
+        &Options::default(),
+        out,
+    )
+}
+
+/// This function converts देवनागरी to Velthuis.
+///
+/// ```
+/// use uast::devanāgarī_to_velthuis;
+///
+/// assert_eq!(
+///     "k.r.s.na.h \"siva.m j~naanam",
+///     devanāgarī_to_velthuis("कृष्णः शिवं ज्ञानम्")
+/// );
+/// ```
+pub fn devanāgarī_to_velthuis(dn: &str) -> String {
+    transliterate(dn, Scheme::Devanāgarī, Scheme::Velthuis)
+}
+
+/// This function converts देवनागरी to Velthuis and fails on any character that Velthuis cannot
+/// write.
+///
+/// ```
+/// use uast::try_devanāgarī_to_velthuis;
+///
+/// assert_eq!("k.lpta.h", try_devanāgarī_to_velthuis("कॢप्तः").unwrap());
+/// assert_eq!("꣠", try_devanāgarī_to_velthuis("अ꣠").unwrap_err().unmappable[0].text);
+/// ```
+pub fn try_devanāgarī_to_velthuis(dn: &str) -> Result<String, TransliterationError> {
+    try_transliterate(dn, Scheme::Devanāgarī, Scheme::Velthuis)
+}
+
+/// This function is [`devanāgarī_to_velthuis`] that appends to `out` instead of allocating.
+///
+/// ```
+/// use uast::devanāgarī_to_velthuis_into;
+///
+/// let mut s = String::from("1. ");
+/// devanāgarī_to_velthuis_into("शिवः", &mut s).unwrap();
+/// assert_eq!("1. \"siva.h", s);
+/// ```
+pub fn devanāgarī_to_velthuis_into<W>(dn: &str, out: &mut W) -> fmt::Result
+where
+    W: Write + ?Sized,
+{
+    transliterate_into(
+        dn,
+        Scheme::Devanāgarī,
+        Scheme::Velthuis,
+        &Options::default(),
+        out,
+    )
+}
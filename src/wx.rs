@@ -0,0 +1,203 @@
+//! WX to देवनागरी and देवनागरी to WX
+
+use crate::{
+    Options, Scheme, TransliterationError,
+    roman::{Letters, Roman},
+    transliterate, transliterate_into, try_transliterate,
+    utils::Context,
+};
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+// WX has no ॡ and no ळ
+const LETTERS: Letters = Letters {
+    vowels: &[
+        ('अ', &["a"]),
+        ('आ', &["A"]),
+        ('इ', &["i"]),
+        ('ई', &["I"]),
+        ('उ', &["u"]),
+        ('ऊ', &["U"]),
+        ('ऋ', &["q"]),
+        ('ऌ', &["L"]),
+        ('ए', &["e"]),
+        ('ऐ', &["E"]),
+        ('ओ', &["o"]),
+        ('औ', &["O"]),
+        ('ॠ', &["Q"]),
+    ],
+    vowel_signs: &[
+        ('ा', &["A"]),
+        ('ि', &["i"]),
+        ('ी', &["I"]),
+        ('ु', &["u"]),
+        ('ू', &["U"]),
+        ('ृ', &["q"]),
+        ('ॄ', &["Q"]),
+        ('े', &["e"]),
+        ('ै', &["E"]),
+        ('ो', &["o"]),
+        ('ौ', &["O"]),
+        ('ॢ', &["L"]),
+    ],
+    consonants: &[
+        ('क', &["k"]),
+        ('ख', &["K"]),
+        ('ग', &["g"]),
+        ('घ', &["G"]),
+        ('ङ', &["f"]),
+        ('च', &["c"]),
+        ('छ', &["C"]),
+        ('ज', &["j"]),
+        ('झ', &["J"]),
+        ('ञ', &["F"]),
+        ('ट', &["t"]),
+        ('ठ', &["T"]),
+        ('ड', &["d"]),
+        ('ढ', &["D"]),
+        ('ण', &["N"]),
+        ('त', &["w"]),
+        ('थ', &["W"]),
+        ('द', &["x"]),
+        ('ध', &["X"]),
+        ('न', &["n"]),
+        ('प', &["p"]),
+        ('फ', &["P"]),
+        ('ब', &["b"]),
+        ('भ', &["B"]),
+        ('म', &["m"]),
+        ('य', &["y"]),
+        ('र', &["r"]),
+        ('ल', &["l"]),
+        ('व', &["v"]),
+        ('श', &["S"]),
+        ('ष', &["R"]),
+        ('स', &["s"]),
+        ('ह', &["h"]),
+    ],
+    misc: &[
+        ('ँ', &["z"]),
+        ('ं', &["M"]),
+        ('ः', &["H"]),
+        ('ऽ', &["'"]),
+        ('।', &["."]),
+        ('॥', &[".."]),
+        ('०', &["0"]),
+        ('१', &["1"]),
+        ('२', &["2"]),
+        ('३', &["3"]),
+        ('४', &["4"]),
+        ('५', &["5"]),
+        ('६', &["6"]),
+        ('७', &["7"]),
+        ('८', &["8"]),
+        ('९', &["9"]),
+    ],
+};
+
+pub(crate) static SCHEME: Roman = Roman {
+    letters: LETTERS,
+    readings: &LETTERS.readings::<{ LETTERS.count() }>(),
+    longest: LETTERS.longest(),
+    inherent: "a",
+    copies: &['ॐ', '॰'],
+    separator: None,
+    escapes: &[],
+    lowercase: false,
+};
+
+pub(crate) fn wx_convertor(wx: &str, ctx: &mut Context, out: &mut dyn Write) -> fmt::Result {
+    SCHEME.read(wx, ctx, out)
+}
+
+pub(crate) fn convertor(dn: &str, ctx: &mut Context, out: &mut dyn Write) -> fmt::Result {
+    SCHEME.write(dn, ctx, out)
+}
+
+/// This function converts WX to देवनागरी.
+///
+/// WX is case sensitive, so `w` is त and `W` is थ.
+///
+/// ```
+/// use uast::wx_to_devanāgarī;
+///
+/// let s = "BUrBuvaH svaH wawsaviwurvareNyaM Bargo xevasya XImahi . Xiyo yo naH pracoxayAw ..";
+/// assert_eq!(
+///     "भूर्भुवः स्वः तत्सवितुर्वरेण्यं भर्गो देवस्य धीमहि । धियो यो नः प्रचोदयात् ॥",
+///     wx_to_devanāgarī(&s)
+/// );
+/// ```
+pub fn wx_to_devanāgarī(wx: &str) -> String {
+    transliterate(wx, Scheme::Wx, Scheme::Devanāgarī)
+}
+
+/// This function converts WX to देवनागरी and fails on any character that is not WX.
+///
+/// ```
+/// use uast::try_wx_to_devanāgarī;
+///
+/// assert_eq!("कॢप्तः", try_wx_to_devanāgarī("kLpwaH").unwrap());
+/// assert_eq!("ā", try_wx_to_devanāgarī("rAma rāma").unwrap_err().unmappable[0].text);
+/// ```
+pub fn try_wx_to_devanāgarī(wx: &str) -> Result<String, TransliterationError> {
+    try_transliterate(wx, Scheme::Wx, Scheme::Devanāgarī)
+}
+
+/// This function is [`wx_to_devanāgarī`] that appends to `out` instead of allocating.
+///
+/// ```
+/// use uast::wx_to_devanāgarī_into;
+///
+/// let mut s = String::from("१. ");
+/// wx_to_devanāgarī_into("SivaH", &mut s).unwrap();
+/// assert_eq!("१. शिवः", s);
+/// ```
+pub fn wx_to_devanāgarī_into<W>(wx: &str, out: &mut W) -> fmt::Result
+where
+    W: Write + ?Sized,
+{
+    transliterate_into(wx, Scheme::Wx, Scheme::Devanāgarī, &Options::default(), out)
+}
+
+/// This function converts देवनागरी to WX.
+///
+/// ```
+/// use uast::devanāgarī_to_wx;
+///
+/// let s = "भूर्भुवः स्वः तत्सवितुर्वरेण्यं भर्गो देवस्य धीमहि । धियो यो नः प्रचोदयात् ॥";
+/// assert_eq!(
+///     "BUrBuvaH svaH wawsaviwurvareNyaM Bargo xevasya XImahi . Xiyo yo naH pracoxayAw ..",
+///     devanāgarī_to_wx(&s)
+/// );
+/// ```
+pub fn devanāgarī_to_wx(dn: &str) -> String {
+    transliterate(dn, Scheme::Devanāgarī, Scheme::Wx)
+}
+
+/// This function converts देवनागरी to WX and fails on any character that WX cannot write.
+///
+/// ```
+/// use uast::try_devanāgarī_to_wx;
+///
+/// assert_eq!("kLpwaH", try_devanāgarī_to_wx("कॢप्तः").unwrap());
+/// assert_eq!("ळ", try_devanāgarī_to_wx("अग्निमीळे").unwrap_err().unmappable[0].text);
+/// ```
+pub fn try_devanāgarī_to_wx(dn: &str) -> Result<String, TransliterationError> {
+    try_transliterate(dn, Scheme::Devanāgarī, Scheme::Wx)
+}
+
+/// This function is [`devanāgarī_to_wx`] that appends to `out` instead of allocating.
+///
+/// ```
+/// use uast::devanāgarī_to_wx_into;
+///
+/// let mut s = String::from("1. ");
+/// devanāgarī_to_wx_into("शिवः", &mut s).unwrap();
+/// assert_eq!("1. SivaH", s);
+/// ```
+pub fn devanāgarī_to_wx_into<W>(dn: &str, out: &mut W) -> fmt::Result
+where
+    W: Write + ?Sized,
+{
+    transliterate_into(dn, Scheme::Devanāgarī, Scheme::Wx, &Options::default(), out)
+}